@@ -1,11 +1,69 @@
-use reqwest::Error;
 use serde::Deserialize;
+use std::fmt;
+
+/// Maximum number of memes the meme API returns in one batch.
+pub const MAX_BATCH_SIZE: u32 = 50;
+
+/// Maximum length of a subreddit name.
+const MAX_SUBREDDIT_LENGTH: usize = 21;
+
+/// Errors that can occur while fetching memes.
+#[derive(Debug)]
+pub enum Error {
+    /// The subreddit name is not a valid Reddit name.
+    InvalidSubreddit(String),
+    /// The HTTP request or the deserialization of the response failed.
+    Request(reqwest::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidSubreddit(name) => write!(f, "Invalid subreddit name: {:?}", name),
+            Error::Request(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Error::Request(err)
+    }
+}
+
+/// Checks whether a name is a valid subreddit name, i.e. matches `^[A-Za-z0-9_]{1,21}$`.
+///
+/// Names are put into the request path, so anything else could reach other endpoints.
+///
+/// # Arguments
+///
+/// * `name` - The subreddit name without the `r/` prefix.
+///
+/// # Returns
+///
+/// `true` if the name can be used in a request.
+pub fn is_valid_subreddit(name: &str) -> bool {
+    (1..=MAX_SUBREDDIT_LENGTH).contains(&name.len())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Represents a batch of memes fetched from the meme API.
+#[derive(Debug, Deserialize)]
+struct Batch {
+    /// The fetched memes.
+    memes: Vec<Meme>,
+}
 
 /// Represents a meme fetched from the meme API.
 #[derive(Debug, Deserialize)]
 pub struct Meme {
     /// The subreddit where the meme was posted.
     pub subreddit: String,
+    /// The link to the Reddit post of the meme.
+    #[serde(rename = "postLink")]
+    pub post_link: String,
     /// The title of the meme.
     pub title: String,
     /// The URL of the meme image.
@@ -24,18 +82,56 @@ pub struct Meme {
 
 /// Fetches a random meme from the meme API.
 ///
+/// # Arguments
+///
+/// * `subreddit` - Optional subreddit to fetch the meme from instead of the default ones.
+///
 /// # Errors
 ///
-/// Returns a `reqwest::Error` if the HTTP request fails or if deserialization of the response fails.
+/// Returns `Error::InvalidSubreddit` for names that are not valid subreddit names, or
+/// `Error::Request` if the HTTP request fails or if deserialization of the response fails.
 ///
 /// # Returns
 ///
-/// Returns a `Result` containing a `Meme` struct if successful, or a `reqwest::Error` if an error occurs.
-pub async fn get() -> Result<Meme, Error> {
-    let response = reqwest::get("https://meme-api.com/gimme")
-        .await?
-        .json::<Meme>()
-        .await?;
+/// Returns a `Result` containing a `Meme` struct if successful, or an `Error` if an error occurs.
+pub async fn get(subreddit: Option<&str>) -> Result<Meme, Error> {
+    let url = match subreddit {
+        Some(subreddit) if !is_valid_subreddit(subreddit) => {
+            return Err(Error::InvalidSubreddit(subreddit.to_string()))
+        }
+        Some(subreddit) => format!("https://meme-api.com/gimme/{}", subreddit),
+        None => String::from("https://meme-api.com/gimme"),
+    };
+    let response = reqwest::get(url).await?.json::<Meme>().await?;
 
     Ok(response)
 }
+
+/// Fetches a batch of random memes from the meme API.
+///
+/// # Arguments
+///
+/// * `subreddit` - Optional subreddit to fetch the memes from instead of the default ones.
+/// * `count` - The number of memes to fetch, capped at `MAX_BATCH_SIZE`.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidSubreddit` for names that are not valid subreddit names, or
+/// `Error::Request` if the HTTP request fails or if deserialization of the response fails.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the fetched memes if successful, or an `Error` if an error occurs.
+pub async fn batch(subreddit: Option<&str>, count: u32) -> Result<Vec<Meme>, Error> {
+    let count = count.clamp(1, MAX_BATCH_SIZE);
+    let url = match subreddit {
+        Some(subreddit) if !is_valid_subreddit(subreddit) => {
+            return Err(Error::InvalidSubreddit(subreddit.to_string()))
+        }
+        Some(subreddit) => format!("https://meme-api.com/gimme/{}/{}", subreddit, count),
+        None => format!("https://meme-api.com/gimme/{}", count),
+    };
+    let response = reqwest::get(url).await?.json::<Batch>().await?;
+
+    Ok(response.memes)
+}
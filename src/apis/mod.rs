@@ -1,4 +1,5 @@
 pub mod meme_api;
+pub mod openmensa;
 pub mod uselessfact;
 
 // use meme_api::{get, Meme};
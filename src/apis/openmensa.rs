@@ -0,0 +1,41 @@
+use chrono::NaiveDate;
+use reqwest::Error;
+use serde::Deserialize;
+
+/// Represents a day in the calendar of a canteen.
+#[derive(Debug, Deserialize)]
+pub struct Day {
+    /// The date in `YYYY-MM-DD` format.
+    pub date: String,
+    /// Whether the canteen is closed on this day.
+    pub closed: bool,
+}
+
+/// Fetches the known days of a canteen, starting at a date.
+///
+/// The `openmensa-rust-interface` crate does not cover this endpoint, so it is queried directly.
+///
+/// # Arguments
+///
+/// * `canteen_id` - The OpenMensa ID of the canteen.
+/// * `start` - The first date to fetch.
+///
+/// # Errors
+///
+/// Returns a `reqwest::Error` if the HTTP request fails or if deserialization of the response fails.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the days if successful, or a `reqwest::Error` if an error occurs.
+pub async fn days(canteen_id: u32, start: NaiveDate) -> Result<Vec<Day>, Error> {
+    let response = reqwest::get(format!(
+        "https://openmensa.org/api/v2/canteens/{}/days?start={}",
+        canteen_id, start
+    ))
+    .await?
+    .error_for_status()?
+    .json::<Vec<Day>>()
+    .await?;
+
+    Ok(response)
+}
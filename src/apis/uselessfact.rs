@@ -1,6 +1,9 @@
 use reqwest::Error;
 use serde::Deserialize;
 
+/// Language codes supported by the useless facts API.
+pub const LANGUAGES: &[&str] = &["de", "en"];
+
 /// Represents a useless fact fetched from the API.
 #[derive(Debug, Deserialize)]
 pub struct UselessFact {
@@ -18,6 +21,19 @@ pub struct UselessFact {
     pub permalink: String,
 }
 
+/// Checks whether the useless facts API supports a language.
+///
+/// # Arguments
+///
+/// * `language` - The language code to check, e.g. "de".
+///
+/// # Returns
+///
+/// `true` if facts are available in the language, otherwise `false`.
+pub fn is_supported_language(language: &str) -> bool {
+    LANGUAGES.contains(&language)
+}
+
 /// Fetches a daily useless fact from the API.
 ///
 /// # Arguments
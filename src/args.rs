@@ -1,4 +1,6 @@
-use clap::Parser;
+use crate::models::PriceGroup;
+use crate::secret::Secret;
+use clap::{Parser, Subcommand};
 
 /// a discord bot with an according cli
 #[derive(Parser, Debug)]
@@ -21,16 +23,32 @@ pub struct Args {
 
     /// Set the token for the Discord bot.
     #[clap(short = 'T', long)]
-    pub token: Option<String>,
+    pub token: Option<Secret>,
+
+    /// Path of the configuration file (defaults to $XDG_CONFIG_HOME/discord-bot/config.toml).
+    #[clap(long)]
+    pub config: Option<String>,
 
     /// Set the path for the .env file containing the token
     #[clap(short = 'E', long)]
     pub env_file: Option<String>,
 
+    /// Register commands only in this development guild (can be repeated).
+    #[clap(short = 'G', long = "guild")]
+    pub guilds: Vec<u64>,
+
+    /// Remove all registered commands (globally and in the development guilds) and exit.
+    #[clap(short = 'U', long)]
+    pub unregister: bool,
+
     /// Display a random meme.
     #[clap(short = 'M', long)]
     pub meme: bool,
 
+    /// Number of memes to display (at most 50).
+    #[clap(short = 'C', long, requires = "meme")]
+    pub count: Option<u32>,
+
     /// Display a daily fact.
     #[clap(short = 'F', long)]
     pub daily_fact: bool,
@@ -38,4 +56,143 @@ pub struct Args {
     /// Display a random fact.
     #[clap(short = 'R', long)]
     pub random_fact: bool,
+
+    /// Language of the displayed fact (e.g. "de" or "en").
+    #[clap(long)]
+    pub lang: Option<String>,
+
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Additional modes of the CLI.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Show and find canteens.
+    Canteens {
+        #[clap(subcommand)]
+        command: CanteensCommand,
+    },
+
+    /// Manage the configuration file.
+    Config {
+        #[clap(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Compare the menus of several canteens side by side.
+    Compare {
+        /// IDs or names of the canteens (at least two).
+        #[clap(num_args = 2.., required = true)]
+        canteens: Vec<String>,
+
+        /// Date of the menus (defaults to "today").
+        #[clap(short = 'D', long, default_value = "today")]
+        date: String,
+
+        /// Price group used to highlight the cheapest meal per category.
+        #[clap(short = 'P', long, value_enum, default_value_t)]
+        price_group: PriceGroup,
+    },
+    /// Find where a dish is served in the configured canteens.
+    Search {
+        /// Text to look for in meal names (case- and umlaut-insensitive).
+        dish: String,
+
+        /// Date of the menus (defaults to "today").
+        #[clap(short = 'D', long, default_value = "today")]
+        date: String,
+    },
+    /// List the cheapest meals of the configured canteens.
+    Cheapest {
+        /// Date of the menus (defaults to "today").
+        #[clap(short = 'D', long, default_value = "today")]
+        date: String,
+
+        /// Price group whose prices are compared; meals without one are ignored.
+        #[clap(short = 'P', long, value_enum, default_value_t)]
+        price_group: PriceGroup,
+
+        /// Number of meals to list.
+        #[clap(short = 'N', long, default_value_t = 5, value_parser = clap::value_parser!(u64).range(1..))]
+        count: u64,
+
+        /// Leave out sides, desserts and drinks.
+        #[clap(long)]
+        full_meals_only: bool,
+    },
+    /// Inspect the Discord bot token.
+    Token {
+        #[clap(subcommand)]
+        command: TokenCommand,
+    },
+}
+
+/// Modes for working with the configuration file.
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Find canteens in the configured cities and add their IDs to the config file.
+    Discover {
+        /// Add all found canteens without asking.
+        #[clap(short = 'A', long)]
+        all: bool,
+    },
+    /// Write a commented configuration template.
+    Init {
+        /// Overwrite an existing config file.
+        #[clap(short = 'F', long)]
+        force: bool,
+    },
+    /// Validate the config file and report problems with their line numbers.
+    Check,
+}
+
+/// Modes for working with canteens.
+#[derive(Subcommand, Debug)]
+pub enum CanteensCommand {
+    /// Show details and the opening days of a canteen.
+    Show {
+        /// The ID or the exact name of the canteen.
+        canteen: String,
+    },
+
+    /// List all canteens.
+    List {
+        /// Print JSON instead of a table.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Search canteens by name, city or distance to a point.
+    Search {
+        /// Part of the canteen name (case-insensitive).
+        #[clap(short = 'N', long)]
+        name: Option<String>,
+
+        /// City the canteens are located in.
+        #[clap(short = 'C', long)]
+        city: Option<String>,
+
+        /// Latitude of the point to search around.
+        #[clap(long, requires = "lng", allow_negative_numbers = true)]
+        lat: Option<f64>,
+
+        /// Longitude of the point to search around.
+        #[clap(long, requires = "lat", allow_negative_numbers = true)]
+        lng: Option<f64>,
+
+        /// Search radius around the point in kilometres.
+        #[clap(short = 'R', long, default_value_t = 5.0)]
+        radius: f64,
+
+        /// Print JSON instead of a table.
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+/// Modes for inspecting the Discord bot token.
+#[derive(Subcommand, Debug)]
+pub enum TokenCommand {
+    /// Show which source the token is taken from and validate its format.
+    Check,
 }
@@ -1,15 +1,38 @@
-use super::commands::{bot, daily_fact, meal, meme, random_fact, Data, Error};
+use super::commands::{
+    alerts, bot, canteen, cheapest, compare, daily_fact, find, help, lunch, meal, meme,
+    random_fact, rate, sync, trivia, Data, Error,
+};
+use super::registration::{register_commands, Registration};
+use super::reload;
+use super::scheduler::Jobs;
+use crate::config::Configs;
 use poise::serenity_prelude as serenity;
 use std::{
-    collections::HashMap,
+    path::PathBuf,
     sync::{Arc, Mutex},
-    time::Duration,
+    time::{Duration, Instant},
 };
+use tokio::sync::watch;
+
+/// Delay before the first restart attempt of a failed client.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound for the delay between two restart attempts.
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Number of consecutive failed attempts after which the supervisor gives up.
+const MAX_ATTEMPTS: u32 = 10;
 
 /// Handles errors encountered by the poise framework.
 async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
     match error {
-        poise::FrameworkError::Setup { error, .. } => panic!("Failed to start bot: {:?}", error),
+        poise::FrameworkError::Setup {
+            error, framework, ..
+        } => {
+            eprintln!("Failed to set up bot: {:?}", error);
+            // Stopping the shards hands control back to the supervisor, which restarts the client.
+            framework.shard_manager().shutdown_all().await;
+        }
         poise::FrameworkError::Command { error, ctx, .. } => {
             println!("Error in command `{}`: {:?}", ctx.command().name, error);
         }
@@ -24,7 +47,23 @@ async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
 /// Sets up framework options for the bot.
 fn setup_framework_options() -> poise::FrameworkOptions<Data, Error> {
     poise::FrameworkOptions {
-        commands: vec![meal(), meme(), daily_fact(), random_fact(), bot()],
+        commands: vec![
+            meal(),
+            rate(),
+            canteen(),
+            compare(),
+            find(),
+            cheapest(),
+            alerts(),
+            lunch(),
+            meme(),
+            daily_fact(),
+            random_fact(),
+            bot(),
+            trivia(),
+            help(),
+            sync(),
+        ],
         prefix_options: poise::PrefixFrameworkOptions {
             prefix: Some("~".into()),
             edit_tracker: Some(Arc::new(poise::EditTracker::for_timespan(
@@ -56,12 +95,20 @@ fn setup_framework_options() -> poise::FrameworkOptions<Data, Error> {
             })
         }),
         skip_checks_for_owners: false,
-        event_handler: |_ctx, event, _framework, _data| {
+        event_handler: |ctx, event, _framework, data| {
             Box::pin(async move {
                 println!(
                     "Got an event in event handler: {:?}",
                     event.snake_case_name()
                 );
+                if let serenity::FullEvent::InteractionCreate {
+                    interaction: serenity::Interaction::Component(press),
+                } = event
+                {
+                    if press.data.custom_id.starts_with(meme::VOTE_PREFIX) {
+                        meme::handle_vote(ctx, press, data).await?;
+                    }
+                }
                 Ok(())
             })
         },
@@ -70,15 +117,16 @@ fn setup_framework_options() -> poise::FrameworkOptions<Data, Error> {
 }
 
 /// Sets up the framework with the provided options.
-fn setup_framework(options: poise::FrameworkOptions<Data, Error>) -> poise::Framework<Data, Error> {
+fn setup_framework(
+    options: poise::FrameworkOptions<Data, Error>,
+    data: Data,
+) -> poise::Framework<Data, Error> {
     poise::Framework::builder()
         .setup(move |ctx, _ready, framework| {
             Box::pin(async move {
                 println!("Logged in as {}", _ready.user.name);
-                poise::builtins::register_globally(ctx, &framework.options().commands).await?;
-                Ok(Data {
-                    votes: Mutex::new(HashMap::new()),
-                })
+                register_commands(ctx, &framework.options().commands, &data.registration).await?;
+                Ok(data)
             })
         })
         .options(options)
@@ -93,29 +141,177 @@ async fn initialize_client(
     let intents =
         serenity::GatewayIntents::non_privileged() | serenity::GatewayIntents::MESSAGE_CONTENT;
 
-    // Check if the token is empty
-    if token.is_empty() {
-        panic!("Invalid token: Please provide a valid bot token. Make sure it starts with 'Bot '.");
-    }
-
     serenity::ClientBuilder::new(token, intents)
         .framework(framework)
         .await
 }
 
-/// Starts the Discord bot with the specified token.
-pub async fn start_bot(token: &str) {
-    let options = setup_framework_options();
-    let framework = setup_framework(options);
-
-    match initialize_client(token, framework).await {
-        Ok(mut client) => {
-            if let Err(e) = client.start().await {
-                panic!("Failed to start the client: {:?}", e);
+/// Waits until the process is asked to terminate.
+///
+/// # Returns
+///
+/// The name of the received signal.
+async fn wait_for_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => tokio::select! {
+                _ = tokio::signal::ctrl_c() => "SIGINT",
+                _ = sigterm.recv() => "SIGTERM",
+            },
+            Err(err) => {
+                eprintln!("Failed to listen for SIGTERM: {}", err);
+                let _ = tokio::signal::ctrl_c().await;
+                "SIGINT"
             }
         }
-        Err(e) => {
-            panic!("Failed to create the client: {:?}", e);
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        "Ctrl+C"
+    }
+}
+
+/// Checks whether a client error can not be resolved by restarting the client.
+///
+/// # Arguments
+///
+/// * `error` - The error returned while creating or running the client.
+///
+/// # Returns
+///
+/// `true` if retrying is pointless, e.g. because the token was rejected.
+fn is_fatal(error: &serenity::Error) -> bool {
+    match error {
+        serenity::Error::Gateway(
+            serenity::GatewayError::InvalidAuthentication
+            | serenity::GatewayError::NoAuthentication
+            | serenity::GatewayError::InvalidGatewayIntents
+            | serenity::GatewayError::DisallowedGatewayIntents
+            | serenity::GatewayError::InvalidShardData
+            | serenity::GatewayError::OverloadedShard,
+        ) => true,
+        serenity::Error::Http(err) => err.status_code().is_some_and(|code| code.as_u16() == 401),
+        _ => false,
+    }
+}
+
+/// Runs a single client until it stops or a shutdown is requested.
+///
+/// # Arguments
+///
+/// * `token` - The Discord bot token.
+/// * `data` - The shared bot state handed to the framework.
+/// * `shutdown` - Receiver that changes once a termination signal arrived.
+///
+/// # Returns
+///
+/// `Ok(())` if the client stopped, otherwise the error that made it stop.
+async fn run_client(
+    token: &str,
+    data: Data,
+    mut shutdown: watch::Receiver<bool>,
+) -> Result<(), serenity::Error> {
+    let framework = setup_framework(setup_framework_options(), data);
+    let mut client = initialize_client(token, framework).await?;
+
+    let shard_manager = client.shard_manager.clone();
+    let shutdown_task = tokio::spawn(async move {
+        if shutdown.wait_for(|requested| *requested).await.is_ok() {
+            shard_manager.shutdown_all().await;
         }
+    });
+
+    let result = client.start().await;
+    shutdown_task.abort();
+    result
+}
+
+/// Starts the Discord bot with the specified token.
+///
+/// The client is supervised: transient failures restart it with exponential backoff,
+/// while SIGINT and SIGTERM shut all shards down and flush the bot state.
+///
+/// # Arguments
+///
+/// * `token` - The Discord bot token.
+/// * `registration` - Where the application commands are registered.
+/// * `configs` - The loaded configuration.
+/// * `config_path` - The path of the config file, watched for changes.
+///
+/// # Returns
+///
+/// `Ok(())` after a requested shutdown, otherwise the error that made the bot give up.
+pub async fn start_bot(
+    token: &str,
+    registration: Registration,
+    configs: Configs,
+    config_path: PathBuf,
+) -> Result<(), Error> {
+    if token.is_empty() {
+        return Err("Invalid token: Please provide a valid bot token.".into());
     }
+
+    let data = Data::load(registration, configs)?;
+    let jobs = Arc::new(Mutex::new(Jobs::spawn(
+        Arc::new(serenity::Http::new(token)),
+        &data,
+    )));
+    let watcher = tokio::spawn(reload::watch_config(
+        config_path,
+        data.clone(),
+        jobs.clone(),
+    ));
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    tokio::spawn(async move {
+        let signal = wait_for_signal().await;
+        println!("Received {}, shutting down...", signal);
+        let _ = shutdown_tx.send(true);
+    });
+
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempts = 0;
+
+    let result = loop {
+        let started = Instant::now();
+        let result = run_client(token, data.clone(), shutdown_rx.clone()).await;
+
+        if *shutdown_rx.borrow() {
+            break Ok(());
+        }
+
+        // A client that ran for a while counts as healthy, so the backoff starts over.
+        if started.elapsed() > MAX_BACKOFF {
+            backoff = INITIAL_BACKOFF;
+            attempts = 0;
+        }
+
+        match result {
+            Err(err) if is_fatal(&err) => break Err(format!("Fatal client error: {}", err).into()),
+            Err(err) => eprintln!("Client stopped with an error: {}", err),
+            Ok(()) => eprintln!("Client stopped unexpectedly"),
+        }
+
+        attempts += 1;
+        if attempts >= MAX_ATTEMPTS {
+            break Err(format!("Giving up after {} failed attempts", attempts).into());
+        }
+
+        println!("Restarting client in {:?}...", backoff);
+        let mut shutdown = shutdown_rx.clone();
+        tokio::select! {
+            _ = tokio::time::sleep(backoff) => {}
+            _ = shutdown.wait_for(|requested| *requested) => break Ok(()),
+        }
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    };
+
+    watcher.abort();
+    jobs.lock().unwrap().abort();
+    data.flush();
+    result
 }
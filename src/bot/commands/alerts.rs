@@ -0,0 +1,376 @@
+use openmensa_rust_interface::get_canteen_by_name;
+use poise::CreateReply;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use super::commands::{all_canteens, reply_language, Context, Error};
+use crate::i18n::{tr, Language, Message};
+
+/// Maximum number of alerts a single user can have.
+const MAX_ALERTS: usize = 25;
+
+/// Maximum length of a message.
+const MESSAGE_LIMIT: usize = 2000;
+
+/// A subscription to meals matching a pattern.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    /// The pattern meal names are matched against.
+    pub pattern: String,
+    /// Whether the pattern is a regular expression instead of a plain substring.
+    pub regex: bool,
+    /// The ID of the watched canteen, `None` to watch the configured canteens.
+    pub canteen: Option<u32>,
+    /// The name of the watched canteen, for display.
+    pub canteen_name: Option<String>,
+    /// The language of the notifications.
+    pub language: Language,
+}
+
+impl Alert {
+    /// Compiles the pattern of the alert.
+    ///
+    /// Both plain and regex patterns are matched case-insensitively.
+    ///
+    /// # Returns
+    ///
+    /// The compiled pattern, or an error if the regular expression is invalid.
+    pub fn matcher(&self) -> Result<Regex, regex::Error> {
+        let pattern = if self.regex {
+            self.pattern.clone()
+        } else {
+            regex::escape(&self.pattern)
+        };
+
+        RegexBuilder::new(&pattern)
+            .case_insensitive(true)
+            .size_limit(1 << 16)
+            .build()
+    }
+}
+
+/// Persisted state of the dish alerts.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AlertState {
+    /// The alerts of each user ID.
+    pub alerts: HashMap<String, Vec<Alert>>,
+    /// Notifications already sent, as `date/user/canteen/meal` keys.
+    pub notified: HashSet<String>,
+}
+
+/// Autocompletes the patterns of the user's alerts.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `partial` - The partial pattern typed so far.
+///
+/// # Returns
+///
+/// The matching patterns.
+async fn own_patterns(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let user = ctx.author().id.to_string();
+    ctx.data().alerts.with(|state| {
+        state
+            .alerts
+            .get(&user)
+            .map(|alerts| {
+                alerts
+                    .iter()
+                    .filter(|alert| alert.pattern.starts_with(partial))
+                    .map(|alert| alert.pattern.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    })
+}
+
+/// Sends an ephemeral reply.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `content` - The text of the reply.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+async fn reply(ctx: Context<'_>, content: impl Into<String>) -> Result<(), Error> {
+    ctx.send(
+        CreateReply::default()
+            .content(content.into())
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Parent command for dish alerts.
+#[poise::command(
+    slash_command,
+    subcommands("add", "list", "remove"),
+    subcommand_required,
+    name_localized("de", "alarme"),
+    description_localized(
+        "de",
+        "Lass dich per DM benachrichtigen, wenn dein Lieblingsgericht auf dem Speiseplan steht."
+    )
+)]
+pub async fn alerts(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Command to get a direct message when a matching meal shows up on a menu.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `pattern` - The text or regular expression to look for in meal names.
+/// * `canteen` - Optional canteen to watch instead of the configured ones.
+/// * `regex` - Whether the pattern is a regular expression.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+#[poise::command(
+    slash_command,
+    name_localized("de", "hinzufügen"),
+    description_localized("de", "Legt einen Alarm für ein Gericht an.")
+)]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "Text to look for in meal names"]
+    #[description_localized("de", "Text, nach dem in Gerichten gesucht wird")]
+    #[name_localized("de", "muster")]
+    #[min_length = 1]
+    #[max_length = 100]
+    pattern: String,
+    #[description = "Canteen to watch (defaults to the configured canteens)"]
+    #[description_localized("de", "Zu beobachtende Mensa (standardmäßig die konfigurierten)")]
+    #[name_localized("de", "mensa")]
+    #[autocomplete = "all_canteens"]
+    canteen: Option<String>,
+    #[description = "Treat the pattern as a regular expression"]
+    #[description_localized("de", "Muster als regulären Ausdruck behandeln")]
+    regex: Option<bool>,
+) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+    // A blank pattern would match every meal
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return reply(ctx, tr(lang, Message::EmptyPattern)).await;
+    }
+
+    let (canteen, canteen_name) = match canteen {
+        Some(name) => match get_canteen_by_name(&name).await {
+            Ok(Some(can)) => (Some(can.id), Some(can.name)),
+            Ok(None) => return reply(ctx, tr(lang, Message::CanteenNotFound)).await,
+            Err(err) => {
+                eprintln!("Error fetching canteen by name: {:?}", err);
+                return reply(ctx, tr(lang, Message::CanteenFetchFailed)).await;
+            }
+        },
+        // Alerts without a canteen watch the configured ones and would never fire otherwise
+        None if ctx.data().configs().locations.canteens.is_empty() => {
+            return reply(ctx, tr(lang, Message::AlertNeedsCanteen)).await
+        }
+        None => (None, None),
+    };
+
+    let alert = Alert {
+        pattern: pattern.to_string(),
+        regex: regex.unwrap_or(false),
+        canteen,
+        canteen_name,
+        language: lang,
+    };
+    if let Err(err) = alert.matcher() {
+        return reply(
+            ctx,
+            format!("{}: {}", tr(lang, Message::InvalidPattern), err),
+        )
+        .await;
+    }
+
+    let user = ctx.author().id.to_string();
+    let added = ctx
+        .data()
+        .alerts
+        .update(|state| {
+            let alerts = state.alerts.entry(user).or_default();
+            if alerts.len() >= MAX_ALERTS {
+                return false;
+            }
+            alerts.push(alert);
+            true
+        })
+        .await;
+
+    if added {
+        reply(ctx, tr(lang, Message::AlertAdded)).await
+    } else {
+        reply(
+            ctx,
+            format!("{} ({})", tr(lang, Message::TooManyAlerts), MAX_ALERTS),
+        )
+        .await
+    }
+}
+
+/// Command to list the user's dish alerts.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+#[poise::command(
+    slash_command,
+    name_localized("de", "liste"),
+    description_localized("de", "Zeigt deine Alarme an.")
+)]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+    let user = ctx.author().id.to_string();
+
+    let lines: Vec<String> = ctx.data().alerts.with(|state| {
+        state
+            .alerts
+            .get(&user)
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(index, alert)| {
+                format!(
+                    "{}. `{}`{} — {}",
+                    index + 1,
+                    alert.pattern,
+                    if alert.regex { " (regex)" } else { "" },
+                    alert
+                        .canteen_name
+                        .as_deref()
+                        .unwrap_or(tr(lang, Message::ConfiguredCanteens))
+                )
+            })
+            .collect()
+    });
+
+    if lines.is_empty() {
+        reply(ctx, tr(lang, Message::NoAlerts)).await
+    } else {
+        reply(ctx, alert_list(&lines, MESSAGE_LIMIT)).await
+    }
+}
+
+/// Joins the lines of the alert list into one message.
+///
+/// Lines that do not fit into the limit are summarised as `+N`.
+///
+/// # Arguments
+///
+/// * `lines` - One line per alert.
+/// * `limit` - The maximum length of the message.
+///
+/// # Returns
+///
+/// The lines separated by newlines.
+fn alert_list(lines: &[String], limit: usize) -> String {
+    // Leaves room for the summary of the lines that do not fit
+    let limit = limit - 8;
+    let mut list = String::new();
+    let mut shown = 0;
+    for line in lines {
+        let separator = if list.is_empty() { 0 } else { 1 };
+        if list.chars().count() + separator + line.chars().count() > limit {
+            break;
+        }
+        if separator == 1 {
+            list.push('\n');
+        }
+        list.push_str(line);
+        shown += 1;
+    }
+    if shown < lines.len() {
+        list.push_str(&format!("\n+{}", lines.len() - shown));
+    }
+    list
+}
+
+/// Command to remove dish alerts.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `pattern` - The pattern of the alerts to remove.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+#[poise::command(
+    slash_command,
+    name_localized("de", "entfernen"),
+    description_localized("de", "Entfernt einen Alarm.")
+)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "Pattern of the alert to remove"]
+    #[description_localized("de", "Muster des zu entfernenden Alarms")]
+    #[name_localized("de", "muster")]
+    #[autocomplete = "own_patterns"]
+    pattern: String,
+) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+    let user = ctx.author().id.to_string();
+
+    let removed = ctx
+        .data()
+        .alerts
+        .update(|state| {
+            let Some(alerts) = state.alerts.get_mut(&user) else {
+                return false;
+            };
+            let count = alerts.len();
+            alerts.retain(|alert| alert.pattern != pattern);
+            let removed = alerts.len() != count;
+            if alerts.is_empty() {
+                state.alerts.remove(&user);
+            }
+            removed
+        })
+        .await;
+
+    if removed {
+        reply(ctx, tr(lang, Message::AlertRemoved)).await
+    } else {
+        reply(ctx, tr(lang, Message::AlertNotFound)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alert_list_summarises_lines_past_the_limit() {
+        let lines: Vec<String> = (0..25).map(|index| format!("{:0>100}", index)).collect();
+
+        let list = alert_list(&lines, MESSAGE_LIMIT);
+
+        assert!(list.chars().count() <= MESSAGE_LIMIT);
+        let shown = list.lines().count() - 1;
+        assert_eq!(
+            list.lines().last(),
+            Some(format!("+{}", 25 - shown).as_str())
+        );
+    }
+
+    #[test]
+    fn alert_list_keeps_short_lists_whole() {
+        let lines = vec![String::from("1. `Pizza`"), String::from("2. `Curry`")];
+
+        assert_eq!(alert_list(&lines, MESSAGE_LIMIT), "1. `Pizza`\n2. `Curry`");
+    }
+}
@@ -0,0 +1,108 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+
+use super::commands::{get_date, guild_canteens, reply_language, timezone, Context, Error};
+use crate::i18n::{self, tr, Message};
+use crate::models::menu;
+use crate::models::price::format_price;
+use crate::models::PriceGroup;
+
+/// Number of meals listed if no count is given.
+const DEFAULT_COUNT: u8 = 5;
+
+/// Maximum length of an embed description.
+const DESCRIPTION_LIMIT: usize = 4096;
+
+/// Command to list the cheapest meals of the configured canteens.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `date` - Optional date string in `%Y-%m-%d` format, defaults to today.
+/// * `price_group` - Optional price group whose prices are compared (defaults to students).
+/// * `count` - Optional number of meals to list (defaults to 5).
+/// * `full_meals_only` - Optionally leave out sides, desserts and drinks.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+#[poise::command(
+    slash_command,
+    name_localized("de", "guenstigste"),
+    description_localized("de", "Zeigt die günstigsten Gerichte der konfigurierten Mensen.")
+)]
+pub async fn cheapest(
+    ctx: Context<'_>,
+    #[description = "Choose a date"]
+    #[description_localized("de", "Wähle ein Datum")]
+    #[name_localized("de", "datum")]
+    date: Option<String>,
+    #[description = "Price group whose prices are compared"]
+    #[description_localized("de", "Preisgruppe, deren Preise verglichen werden")]
+    #[name_localized("de", "preisgruppe")]
+    price_group: Option<PriceGroup>,
+    #[description = "Number of meals to list"]
+    #[description_localized("de", "Anzahl der angezeigten Gerichte")]
+    #[name_localized("de", "anzahl")]
+    #[min = 1]
+    #[max = 25]
+    count: Option<u8>,
+    #[description = "Leave out sides, desserts and drinks"]
+    #[description_localized("de", "Beilagen, Desserts und Getränke weglassen")]
+    #[name_localized("de", "nur_hauptgerichte")]
+    full_meals_only: Option<bool>,
+) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+    let date = get_date(date, timezone(ctx));
+    let price_group = price_group.unwrap_or_default();
+    let count = count.unwrap_or(DEFAULT_COUNT).clamp(1, 25);
+
+    let Some(canteens) = guild_canteens(ctx, lang).await? else {
+        return Ok(());
+    };
+
+    let cheapest = menu::cheapest(
+        menu::fetch_all(&canteens, date).await,
+        price_group,
+        count as usize,
+        full_meals_only.unwrap_or(false),
+    );
+
+    if cheapest.is_empty() {
+        ctx.say(format!(
+            "{} ({})",
+            tr(lang, Message::NoPricedMeals),
+            i18n::date(lang, date)
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let mut description = String::new();
+    for (rank, (served, price)) in cheapest.iter().enumerate() {
+        let line = format!(
+            "{}. **{}** — {} · {}\n",
+            rank + 1,
+            format_price(Some(*price)),
+            served.meal.name,
+            served.canteen
+        );
+        if description.len() + line.len() > DESCRIPTION_LIMIT {
+            break;
+        }
+        description.push_str(&line);
+    }
+
+    let embed = CreateEmbed::new()
+        .title(format!(
+            "{} · {}",
+            tr(lang, Message::CheapestMeals),
+            i18n::date(lang, date)
+        ))
+        .description(description)
+        .footer(CreateEmbedFooter::new(price_group.label(lang)));
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
@@ -1,16 +1,116 @@
 use chrono::{NaiveDate, Utc};
+use chrono_tz::Tz;
 use futures::future;
 use futures::stream::{self, Stream, StreamExt};
-use openmensa_rust_interface::{get_all_canteens, get_canteen_by_name, get_meals};
+use openmensa_rust_interface::{
+    get_all_canteens, get_canteen_by_name, get_canteens_by_ids, get_meals, Canteen,
+};
+use poise::serenity_prelude::{self as serenity, Mentionable};
 use poise::CreateReply;
 use serenity::builder::CreateEmbed;
-use std::{collections::HashMap, sync::Mutex};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
-use crate::apis::{meme_api, uselessfact};
+use super::alerts::AlertState;
+use super::meme::PostedMeme;
+use crate::apis::meme_api::Meme;
+use crate::apis::uselessfact;
+use crate::bot::embeds::fact_embed;
+use crate::bot::registration::{register_commands, Registration};
+use crate::clock;
+use crate::config::Configs;
+use crate::i18n::{self, tr, Language, Message};
+use crate::models::CanteenInfo;
+use crate::ratings::{self, Rating, Ratings};
+use crate::storage::Store;
 
 /// Struct holding shared data for bot commands.
+///
+/// All fields are reference counted, so the same state survives client restarts.
+#[derive(Clone)]
 pub struct Data {
-    pub votes: Mutex<HashMap<String, u32>>,
+    /// Memes posted within the last week and their votes, keyed by message ID.
+    pub votes: Arc<Store<HashMap<String, PostedMeme>>>,
+    /// IDs of the facts already announced, keyed by channel ID.
+    pub fact_history: Arc<Store<HashMap<String, Vec<String>>>>,
+    /// URLs of recently posted memes, keyed by guild ID (channel ID in direct messages).
+    pub meme_history: Arc<Store<HashMap<String, Vec<String>>>>,
+    /// Prefetched memes, so `/meme random` does not have to wait for the API.
+    pub meme_pool: Arc<tokio::sync::Mutex<Vec<Meme>>>,
+    /// Dish alerts of all users and the notifications already sent.
+    pub alerts: Arc<Store<AlertState>>,
+    /// Ratings of meals by users.
+    pub meal_ratings: Arc<Store<Ratings>>,
+    /// Trivia points per user ID, keyed by guild ID.
+    pub trivia_scores: Arc<Store<HashMap<String, HashMap<String, u32>>>>,
+    /// Where the application commands are registered.
+    pub registration: Registration,
+    /// The loaded configuration, swapped as a whole when the config file is reloaded.
+    pub configs: Arc<RwLock<Arc<Configs>>>,
+}
+
+impl Data {
+    /// Loads the persisted state of the bot from the data directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `registration` - Where the application commands are registered.
+    /// * `configs` - The loaded configuration.
+    ///
+    /// # Returns
+    ///
+    /// The bot state, otherwise returns an error message as a `String` if a store can not be opened.
+    pub fn load(registration: Registration, configs: Configs) -> Result<Self, String> {
+        Ok(Data {
+            votes: Arc::new(Store::open("votes.json")?),
+            fact_history: Arc::new(Store::open("fact_history.json")?),
+            meme_history: Arc::new(Store::open("meme_history.json")?),
+            meme_pool: Arc::new(tokio::sync::Mutex::new(Vec::new())),
+            alerts: Arc::new(Store::open("alerts.json")?),
+            meal_ratings: Arc::new(Store::open(ratings::RATINGS_FILE)?),
+            trivia_scores: Arc::new(Store::open("trivia_scores.json")?),
+            registration,
+            configs: Arc::new(RwLock::new(Arc::new(configs))),
+        })
+    }
+
+    /// Returns the current configuration.
+    ///
+    /// The returned snapshot stays consistent even if the configuration is reloaded meanwhile.
+    pub fn configs(&self) -> Arc<Configs> {
+        self.configs.read().unwrap().clone()
+    }
+
+    /// Replaces the configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `configs` - The new configuration.
+    ///
+    /// # Returns
+    ///
+    /// The replaced configuration.
+    pub fn replace_configs(&self, configs: Configs) -> Arc<Configs> {
+        std::mem::replace(&mut *self.configs.write().unwrap(), Arc::new(configs))
+    }
+
+    /// Writes all persisted state to disk.
+    pub fn flush(&self) {
+        let results = [
+            self.votes.flush(),
+            self.fact_history.flush(),
+            self.meme_history.flush(),
+            self.alerts.flush(),
+            self.meal_ratings.flush(),
+            self.trivia_scores.flush(),
+        ];
+        for err in results.into_iter().filter_map(Result::err) {
+            eprintln!("{}", err);
+        }
+    }
 }
 
 /// Alias for the error type used in this module.
@@ -19,6 +119,80 @@ pub type Error = Box<dyn std::error::Error + Send + Sync>;
 /// Alias for the context type used in this module.
 pub type Context<'a> = poise::Context<'a, Data, Error>;
 
+/// Resolves the language to reply in.
+///
+/// The guild setting wins, followed by the user's Discord locale and the configured default.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+///
+/// # Returns
+///
+/// The language to use for replies.
+pub fn reply_language(ctx: Context<'_>) -> Language {
+    let configs = ctx.data().configs();
+
+    ctx.guild_id()
+        .and_then(|guild_id| configs.guild(guild_id.get()))
+        .and_then(|guild| guild.language)
+        .or_else(|| ctx.locale().and_then(Language::from_code))
+        .unwrap_or(configs.language)
+}
+
+/// Resolves the timezone that decides what "today" is.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+///
+/// # Returns
+///
+/// The timezone of the guild, or the configured timezone outside of guilds.
+pub fn timezone(ctx: Context<'_>) -> Tz {
+    ctx.data()
+        .configs()
+        .timezone_for(ctx.guild_id().map(|guild_id| guild_id.get()))
+}
+
+/// Resolves and validates the language of a useless fact.
+///
+/// See [`Configs::fact_language`], falling back to the language used for replies.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `requested` - The language chosen by the user, if any.
+///
+/// # Returns
+///
+/// The language code, or a localized error message if the language is not supported.
+pub fn fact_language(ctx: Context<'_>, requested: Option<String>) -> Result<String, String> {
+    ctx.data()
+        .configs()
+        .fact_language(requested.as_deref(), reply_language(ctx))
+}
+
+/// Autocompletes the languages supported by the useless facts API.
+///
+/// # Arguments
+///
+/// * `_ctx` - The context for executing the command.
+/// * `partial` - The partial language code typed so far.
+///
+/// # Returns
+///
+/// The supported language codes starting with the partial input.
+pub async fn fact_languages<'a>(
+    _ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    uselessfact::LANGUAGES
+        .iter()
+        .filter(move |code| code.starts_with(&partial.to_lowercase()))
+        .map(|code| code.to_string())
+}
+
 /// Streams names of all canteens matching a partial name.
 ///
 /// # Arguments
@@ -29,7 +203,10 @@ pub type Context<'a> = poise::Context<'a, Data, Error>;
 /// # Returns
 ///
 /// A stream of canteen names matching the partial name.
-async fn all_canteens<'a>(_ctx: Context<'_>, partial: &'a str) -> impl Stream<Item = String> + 'a {
+pub async fn all_canteens<'a>(
+    _ctx: Context<'_>,
+    partial: &'a str,
+) -> impl Stream<Item = String> + 'a {
     match get_all_canteens().await {
         Ok(canteens) => {
             let filtered_stream = stream::iter(canteens)
@@ -44,46 +221,69 @@ async fn all_canteens<'a>(_ctx: Context<'_>, partial: &'a str) -> impl Stream<It
     }
 }
 
-/// Checks if a given string is a valid date in the format `%Y-%m-%d`.
+/// Fetches the canteens configured for the guild of the invocation.
+///
+/// Defers the reply, as fetching the menus of several canteens takes a while.
+/// If there are no canteens or they can not be fetched, the user is told so.
 ///
 /// # Arguments
 ///
-/// * `date_str` - The date string to validate.
+/// * `ctx` - The context for executing the command.
+/// * `lang` - The language of the replies.
 ///
 /// # Returns
 ///
-/// `true` if the date string is valid, otherwise `false`.
-fn is_valid_date(date_str: &str) -> bool {
-    if let Ok(_) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
-        true
-    } else {
-        false
+/// The canteens, or `None` if the command should stop.
+pub async fn guild_canteens(
+    ctx: Context<'_>,
+    lang: Language,
+) -> Result<Option<Vec<Canteen>>, Error> {
+    let ids = ctx
+        .data()
+        .configs()
+        .canteens_for(ctx.guild_id().map(|guild_id| guild_id.get()))
+        .to_vec();
+    if ids.is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content(tr(lang, Message::NoGuildCanteens))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(None);
+    }
+
+    ctx.defer().await?;
+
+    match get_canteens_by_ids(ids).await {
+        Ok(canteens) => Ok(Some(canteens)),
+        Err(err) => {
+            eprintln!("Error fetching canteens by IDs: {:?}", err);
+            ctx.say(tr(lang, Message::CanteenFetchFailed)).await?;
+            Ok(None)
+        }
     }
 }
 
-/// Retrieves the date string for querying meals, defaults to today's date if invalid.
+/// Parses the date for querying meals, defaults to today's date if it is missing or invalid.
 ///
 /// # Arguments
 ///
-/// * `date` - Optional date string provided by the user.
+/// * `date` - Optional date string in `%Y-%m-%d` format provided by the user.
+/// * `timezone` - The timezone that decides what today's date is.
 ///
 /// # Returns
 ///
-/// The validated date string to use for querying meals.
-fn get_date(date: Option<String>) -> String {
-    let date_now = Utc::now().date_naive();
-    let date_str;
+/// The date to use for querying meals.
+pub fn get_date(date: Option<String>, timezone: Tz) -> NaiveDate {
+    let Some(date) = date else {
+        return clock::today(timezone);
+    };
 
-    if let Some(date) = date {
-        if is_valid_date(&date) {
-            return date;
-        }
+    NaiveDate::parse_from_str(&date, "%Y-%m-%d").unwrap_or_else(|_| {
         eprintln!("Invalid date entered: {}", date);
-        return date_now.to_string();
-    }
-
-    date_str = date_now.to_string();
-    date_str
+        clock::today(timezone)
+    })
 }
 
 /// Command to fetch and display meals from a specified canteen on a given date.
@@ -97,54 +297,94 @@ fn get_date(date: Option<String>) -> String {
 /// # Returns
 ///
 /// A result indicating success (`Ok`) or an error (`Err`) if fetching or sending fails.
-#[poise::command(slash_command)]
+#[poise::command(
+    slash_command,
+    description_localized("de", "Zeigt die Gerichte einer Mensa an einem Tag an.")
+)]
 pub async fn meal(
     ctx: Context<'_>,
     #[description = "choose a canteen"]
+    #[description_localized("de", "Wähle eine Mensa")]
+    #[name_localized("de", "mensa")]
     #[autocomplete = "all_canteens"]
     canteen: String,
-    #[description = "Choose a date"] date: Option<String>,
+    #[description = "Choose a date"]
+    #[description_localized("de", "Wähle ein Datum")]
+    #[name_localized("de", "datum")]
+    date: Option<String>,
 ) -> Result<(), Error> {
-    let date_str = get_date(date);
+    let lang = reply_language(ctx);
+    let date = get_date(date, timezone(ctx));
 
     // Ensure canteen exists
-    let meals = match get_canteen_by_name(&canteen).await {
-        Ok(Some(can)) => get_meals(&can, &date_str).await?,
+    let canteen = match get_canteen_by_name(&canteen).await {
+        Ok(Some(can)) => can,
         Ok(None) => {
             eprintln!("Canteen not found: {}", canteen);
-            ctx.say("Canteen not found.").await?;
+            ctx.say(tr(lang, Message::CanteenNotFound)).await?;
             return Ok(());
         }
         Err(err) => {
             eprintln!("Error fetching canteen by name: {:?}", err);
-            ctx.say("Failed to fetch canteen.").await?;
+            ctx.say(tr(lang, Message::CanteenFetchFailed)).await?;
             return Ok(());
         }
     };
 
+    let meals = get_meals(&canteen, &date.to_string()).await?;
+    let date = i18n::date(lang, date);
+
     if meals.is_empty() {
-        ctx.say("No meals found for the selected canteen.").await?;
+        ctx.say(format!("{} ({})", tr(lang, Message::NoMeals), date))
+            .await?;
         return Ok(());
     }
 
     // Create reply with all embeds
-    let mut reply = CreateReply::default();
+    let mut reply = CreateReply::default().content(format!("**{}** · {}", canteen.name, date));
     for meal in &meals {
         let price_info = format!(
-            "Students: {}\nEmployees: {}\nPupils: {}\nOthers: {}",
+            "{}: {}\n{}: {}\n{}: {}\n{}: {}",
+            tr(lang, Message::Students),
             meal.prices.students.unwrap_or_default(),
+            tr(lang, Message::Employees),
             meal.prices.employees.unwrap_or_default(),
+            tr(lang, Message::Pupils),
             meal.prices.pupils.unwrap_or_default(),
+            tr(lang, Message::Others),
             meal.prices.others.unwrap_or_default(),
         );
 
         let notes = meal.notes.join(", ");
 
-        let embed = CreateEmbed::new().title(&meal.name).field(
-            &format!("Category: {}", meal.category),
-            &format!("Prices:\n{}\nNotes: {}", price_info, notes),
-            false,
-        );
+        let (rating, comment) = ctx.data().meal_ratings.with(|all_ratings| {
+            (
+                ratings::format_average(all_ratings, canteen.id, &meal.name),
+                ratings::latest_comment(all_ratings, canteen.id, &meal.name),
+            )
+        });
+
+        let mut embed = CreateEmbed::new()
+            .title(&meal.name)
+            .field(
+                format!("{}: {}", tr(lang, Message::Category), meal.category),
+                format!(
+                    "{}:\n{}\n{}: {}",
+                    tr(lang, Message::Prices),
+                    price_info,
+                    tr(lang, Message::Notes),
+                    notes
+                ),
+                false,
+            )
+            .field(tr(lang, Message::Rating), rating, true);
+        if let Some(comment) = comment {
+            embed = embed.field(
+                tr(lang, Message::LatestReview),
+                format!("> {}", comment),
+                true,
+            );
+        }
 
         reply = reply.embed(embed);
     }
@@ -155,28 +395,216 @@ pub async fn meal(
     Ok(())
 }
 
-/// Command to fetch and display a meme.
+/// Command to show details of a canteen and its opening days in the coming week.
 ///
 /// # Arguments
 ///
 /// * `ctx` - The context for executing the command.
+/// * `canteen` - The name of the canteen.
 ///
 /// # Returns
 ///
-/// A result indicating success (`Ok`) or an error (`Err`) if fetching or sending fails.
-#[poise::command(slash_command)]
-pub async fn meme(ctx: Context<'_>) -> Result<(), Error> {
-    match meme_api::get().await {
-        Ok(meme) => {
-            ctx.say(meme.url).await?;
-            Ok(())
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+#[poise::command(
+    slash_command,
+    name_localized("de", "mensa"),
+    description_localized("de", "Zeigt Details und Öffnungstage einer Mensa an.")
+)]
+pub async fn canteen(
+    ctx: Context<'_>,
+    #[description = "choose a canteen"]
+    #[description_localized("de", "Wähle eine Mensa")]
+    #[name_localized("de", "name")]
+    #[autocomplete = "all_canteens"]
+    name: String,
+) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+
+    let canteen = match get_canteen_by_name(&name).await {
+        Ok(Some(can)) => can,
+        Ok(None) => {
+            eprintln!("Canteen not found: {}", name);
+            ctx.say(tr(lang, Message::CanteenNotFound)).await?;
+            return Ok(());
         }
         Err(err) => {
-            eprintln!("Error fetching meme: {:?}", err);
-            ctx.say("Failed to fetch meme.").await?;
-            Err(err.into())
+            eprintln!("Error fetching canteen by name: {:?}", err);
+            ctx.say(tr(lang, Message::CanteenFetchFailed)).await?;
+            return Ok(());
+        }
+    };
+
+    let info = CanteenInfo::fetch(canteen, clock::today(timezone(ctx))).await;
+    let mut embed = CreateEmbed::new().title(&info.canteen.name);
+    if let Some(link) = info.map_link() {
+        embed = embed.url(link);
+    }
+    for (name, value) in info.fields(lang) {
+        // The opening days are the only multi-line value and read better in full width
+        let inline = !value.contains('\n');
+        embed = embed.field(name, value, inline);
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Autocompletes today's meals of the canteen chosen in the same command.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `partial` - A partial meal name used to filter meals.
+///
+/// # Returns
+///
+/// The names of matching meals, empty if no canteen was chosen yet.
+async fn todays_meals(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let poise::Context::Application(app_ctx) = ctx else {
+        return Vec::new();
+    };
+    let canteen = app_ctx
+        .args
+        .iter()
+        .find_map(|option| match (option.name, &option.value) {
+            ("canteen", serenity::ResolvedValue::String(name)) => Some(name.to_string()),
+            _ => None,
+        });
+
+    let Some(canteen) = canteen else {
+        return Vec::new();
+    };
+    let Ok(Some(canteen)) = get_canteen_by_name(&canteen).await else {
+        return Vec::new();
+    };
+
+    let today = clock::today(timezone(ctx)).to_string();
+    let partial = partial.to_lowercase();
+    match get_meals(&canteen, &today).await {
+        Ok(meals) => meals
+            .into_iter()
+            .map(|meal| meal.name)
+            // Discord rejects autocomplete choices longer than 100 characters
+            .filter(|name| name.chars().count() <= 100 && name.to_lowercase().contains(&partial))
+            .take(25)
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Command to rate a meal of a canteen.
+///
+/// A user's new rating of a meal replaces their previous one. Only meals on today's
+/// menu of the canteen can be rated.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `canteen` - The name of the canteen serving the meal.
+/// * `meal` - The name of the meal.
+/// * `rating` - The number of stars from 1 to 5.
+/// * `comment` - Optional review text.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+#[poise::command(
+    slash_command,
+    name_localized("de", "bewerten"),
+    description_localized("de", "Bewertet ein Gericht einer Mensa.")
+)]
+pub async fn rate(
+    ctx: Context<'_>,
+    #[description = "choose a canteen"]
+    #[description_localized("de", "Wähle eine Mensa")]
+    #[name_localized("de", "mensa")]
+    #[autocomplete = "all_canteens"]
+    canteen: String,
+    #[description = "The meal to rate"]
+    #[description_localized("de", "Das zu bewertende Gericht")]
+    #[name_localized("de", "gericht")]
+    #[autocomplete = "todays_meals"]
+    meal: String,
+    #[description = "Stars from 1 to 5"]
+    #[description_localized("de", "Sterne von 1 bis 5")]
+    #[name_localized("de", "bewertung")]
+    #[min = 1]
+    #[max = 5]
+    rating: u8,
+    #[description = "A short review"]
+    #[description_localized("de", "Eine kurze Rezension")]
+    #[name_localized("de", "kommentar")]
+    #[max_length = 300]
+    comment: Option<String>,
+) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+
+    let canteen = match get_canteen_by_name(&canteen).await {
+        Ok(Some(can)) => can,
+        Ok(None) => {
+            eprintln!("Canteen not found: {}", canteen);
+            ctx.say(tr(lang, Message::CanteenNotFound)).await?;
+            return Ok(());
+        }
+        Err(err) => {
+            eprintln!("Error fetching canteen by name: {:?}", err);
+            ctx.say(tr(lang, Message::CanteenFetchFailed)).await?;
+            return Ok(());
+        }
+    };
+
+    let today = clock::today(timezone(ctx)).to_string();
+    let on_menu = match get_meals(&canteen, &today).await {
+        Ok(meals) => meals.iter().any(|served| served.name == meal),
+        Err(err) => {
+            eprintln!("Error fetching meals: {:?}", err);
+            ctx.say(tr(lang, Message::MealsFetchFailed)).await?;
+            return Ok(());
         }
+    };
+    if !on_menu {
+        ctx.send(
+            CreateReply::default()
+                .content(tr(lang, Message::MealNotOnMenu))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
     }
+
+    let user = ctx.author().id.to_string();
+    let average = ctx
+        .data()
+        .meal_ratings
+        .update(|all_ratings| {
+            all_ratings
+                .entry(ratings::key(canteen.id, &meal))
+                .or_default()
+                .insert(
+                    user,
+                    Rating {
+                        stars: rating,
+                        comment,
+                        rated_at: Utc::now().timestamp(),
+                    },
+                );
+            ratings::format_average(all_ratings, canteen.id, &meal)
+        })
+        .await;
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "{} **{}** ({}): {}",
+                tr(lang, Message::RatingSaved),
+                meal,
+                canteen.name,
+                average
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
 }
 
 /// Command to fetch and display a daily useless fact.
@@ -184,47 +612,210 @@ pub async fn meme(ctx: Context<'_>) -> Result<(), Error> {
 /// # Arguments
 ///
 /// * `ctx` - The context for executing the command.
+/// * `language` - Optional language code of the fact.
 ///
 /// # Returns
 ///
 /// A result indicating success (`Ok`) or an error (`Err`) if fetching or sending fails.
-#[poise::command(slash_command)]
-pub async fn daily_fact(ctx: Context<'_>) -> Result<(), Error> {
-    match uselessfact::daily(Some(String::from("de"))).await {
+#[poise::command(
+    slash_command,
+    name_localized("de", "fakt_des_tages"),
+    description_localized("de", "Zeigt den nutzlosen Fakt des Tages an.")
+)]
+pub async fn daily_fact(
+    ctx: Context<'_>,
+    #[description = "Language of the fact"]
+    #[description_localized("de", "Sprache des Fakts")]
+    #[name_localized("de", "sprache")]
+    #[autocomplete = "fact_languages"]
+    language: Option<String>,
+) -> Result<(), Error> {
+    let code = match fact_language(ctx, language) {
+        Ok(code) => code,
+        Err(message) => {
+            ctx.send(CreateReply::default().content(message).ephemeral(true))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let lang = reply_language(ctx);
+    match uselessfact::daily(Some(code)).await {
         Ok(fact) => {
-            ctx.say(fact.text).await?;
+            let embed = fact_embed(&fact, tr(lang, Message::FactOfTheDay), lang);
+            ctx.send(CreateReply::default().embed(embed)).await?;
             Ok(())
         }
         Err(err) => {
             eprintln!("Error fetching daily fact: {:?}", err);
-            ctx.say("Failed to fetch daily fact.").await?;
+            ctx.say(tr(lang, Message::DailyFactFetchFailed)).await?;
             Err(err.into())
         }
     }
 }
 
+/// Creates the buttons shown below a random fact.
+///
+/// # Arguments
+///
+/// * `ctx_id` - The ID of the invocation, used as prefix for the button IDs.
+/// * `lang` - The language of the button labels.
+/// * `shareable` - Whether to show the share button.
+///
+/// # Returns
+///
+/// A row of buttons.
+fn random_fact_buttons(ctx_id: u64, lang: Language, shareable: bool) -> serenity::CreateActionRow {
+    let mut buttons = vec![serenity::CreateButton::new(format!("{}reroll", ctx_id))
+        .label(tr(lang, Message::AnotherOne))
+        .emoji('🎲')];
+
+    if shareable {
+        buttons.push(
+            serenity::CreateButton::new(format!("{}share", ctx_id))
+                .label(tr(lang, Message::Share))
+                .style(serenity::ButtonStyle::Success),
+        );
+    }
+
+    serenity::CreateActionRow::Buttons(buttons)
+}
+
 /// Command to fetch and display a random useless fact.
 ///
+/// The reply has a button to swap in another fact. Private replies also get
+/// a button to share the fact publicly in the channel.
+///
 /// # Arguments
 ///
 /// * `ctx` - The context for executing the command.
+/// * `language` - Optional language code of the fact.
+/// * `private` - Whether only the user should see the reply.
 ///
 /// # Returns
 ///
 /// A result indicating success (`Ok`) or an error (`Err`) if fetching or sending fails.
-#[poise::command(slash_command)]
-pub async fn random_fact(ctx: Context<'_>) -> Result<(), Error> {
-    match uselessfact::random(Some(String::from("de"))).await {
-        Ok(fact) => {
-            ctx.say(fact.text).await?;
-            Ok(())
+#[poise::command(
+    slash_command,
+    name_localized("de", "zufallsfakt"),
+    description_localized("de", "Zeigt einen zufälligen nutzlosen Fakt an.")
+)]
+pub async fn random_fact(
+    ctx: Context<'_>,
+    #[description = "Language of the fact"]
+    #[description_localized("de", "Sprache des Fakts")]
+    #[name_localized("de", "sprache")]
+    #[autocomplete = "fact_languages"]
+    language: Option<String>,
+    #[description = "Only show the fact to you"]
+    #[description_localized("de", "Fakt nur dir anzeigen")]
+    #[name_localized("de", "privat")]
+    private: Option<bool>,
+) -> Result<(), Error> {
+    let code = match fact_language(ctx, language) {
+        Ok(code) => code,
+        Err(message) => {
+            ctx.send(CreateReply::default().content(message).ephemeral(true))
+                .await?;
+            return Ok(());
         }
+    };
+
+    let lang = reply_language(ctx);
+    let private = private.unwrap_or(false);
+    let mut fact = match uselessfact::random(Some(code.clone())).await {
+        Ok(fact) => fact,
         Err(err) => {
             eprintln!("Error fetching random fact: {:?}", err);
-            ctx.say("Failed to fetch random fact.").await?;
-            Err(err.into())
+            ctx.say(tr(lang, Message::RandomFactFetchFailed)).await?;
+            return Err(err.into());
+        }
+    };
+
+    let ctx_id = ctx.id();
+    let author = ctx.author().id;
+    let title = tr(lang, Message::RandomFact);
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .embed(fact_embed(&fact, title, lang))
+                .components(vec![random_fact_buttons(ctx_id, lang, private)])
+                .ephemeral(private),
+        )
+        .await?;
+
+    while let Some(press) = serenity::ComponentInteractionCollector::new(ctx)
+        .filter(move |press| press.data.custom_id.starts_with(&ctx_id.to_string()))
+        .timeout(Duration::from_secs(300))
+        .await
+    {
+        // Public facts are visible to everyone, but only the author may change or share them
+        if press.user.id != author {
+            press
+                .create_response(
+                    ctx,
+                    serenity::CreateInteractionResponse::Message(
+                        serenity::CreateInteractionResponseMessage::new()
+                            .content(tr(lang, Message::NotYourButtons))
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+            continue;
+        }
+
+        if press.data.custom_id.ends_with("reroll") {
+            let response = match uselessfact::random(Some(code.clone())).await {
+                Ok(new_fact) => {
+                    fact = new_fact;
+                    serenity::CreateInteractionResponse::UpdateMessage(
+                        serenity::CreateInteractionResponseMessage::new()
+                            .embed(fact_embed(&fact, title, lang)),
+                    )
+                }
+                Err(err) => {
+                    eprintln!("Error fetching random fact: {:?}", err);
+                    serenity::CreateInteractionResponse::Message(
+                        serenity::CreateInteractionResponseMessage::new()
+                            .content(tr(lang, Message::RandomFactFetchFailed))
+                            .ephemeral(true),
+                    )
+                }
+            };
+            press.create_response(ctx, response).await?;
+        } else if press.data.custom_id.ends_with("share") {
+            let message = serenity::CreateMessage::new()
+                .content(format!(
+                    "{} {}",
+                    press.user.mention(),
+                    tr(lang, Message::SharedFact)
+                ))
+                .embed(fact_embed(&fact, title, lang));
+            let response = match press.channel_id.send_message(ctx, message).await {
+                Ok(_) => serenity::CreateInteractionResponse::Acknowledge,
+                Err(err) => {
+                    eprintln!("Error sharing random fact: {:?}", err);
+                    serenity::CreateInteractionResponse::Message(
+                        serenity::CreateInteractionResponseMessage::new()
+                            .content(tr(lang, Message::ShareFailed))
+                            .ephemeral(true),
+                    )
+                }
+            };
+            press.create_response(ctx, response).await?;
         }
     }
+
+    // Remove the buttons once they stop working
+    reply
+        .edit(
+            ctx,
+            CreateReply::default()
+                .embed(fact_embed(&fact, title, lang))
+                .components(vec![]),
+        )
+        .await?;
+    Ok(())
 }
 
 /// Command to show the origin of the Bot
@@ -236,8 +827,40 @@ pub async fn random_fact(ctx: Context<'_>) -> Result<(), Error> {
 /// # Returns
 ///
 /// A result indicating success (`Ok`) or an error (`Err`) if fetching or sending fails.
-#[poise::command(slash_command)]
+#[poise::command(
+    slash_command,
+    description_localized("de", "Zeigt, woher der Bot stammt.")
+)]
 pub async fn bot(ctx: Context<'_>) -> Result<(), Error> {
     ctx.say("https://github.com/floork/discord-bot.git").await?;
     Ok(())
 }
+
+/// Owner command to re-register all application commands without restarting the bot.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if registering or sending fails.
+#[poise::command(prefix_command, owners_only, hide_in_help)]
+pub async fn sync(ctx: Context<'_>) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+    let commands = &ctx.framework().options().commands;
+    register_commands(ctx, commands, &ctx.data().registration).await?;
+
+    let target = match &ctx.data().registration {
+        Registration::Global => String::from(tr(lang, Message::Globally)),
+        Registration::Guilds(guilds) => format!("{} {}", guilds.len(), tr(lang, Message::Servers)),
+    };
+    ctx.say(format!(
+        "{}: {} ({})",
+        tr(lang, Message::CommandsRegistered),
+        commands.len(),
+        target
+    ))
+    .await?;
+    Ok(())
+}
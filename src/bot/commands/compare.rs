@@ -0,0 +1,215 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serenity::builder::CreateEmbed;
+
+use super::commands::{all_canteens, get_date, reply_language, timezone, Context, Error};
+use crate::i18n::{self, tr, Message};
+use crate::models::comparison::CategoryRow;
+use crate::models::price::format_price;
+use crate::models::{canteen, Comparison, PriceGroup};
+
+/// Maximum length of an embed title or field name.
+const NAME_LIMIT: usize = 256;
+
+/// Maximum length of an embed field value.
+const FIELD_LIMIT: usize = 1024;
+
+/// Maximum number of embeds in a single message.
+const EMBEDS_PER_MESSAGE: usize = 10;
+
+/// Maximum total length of all embeds in a single message.
+const MESSAGE_EMBED_LIMIT: usize = 6000;
+
+/// Formats the meals of one canteen in a category for an embed field.
+///
+/// # Arguments
+///
+/// * `row` - The category row.
+/// * `canteen` - The index of the canteen.
+/// * `price_group` - The price group whose prices are shown.
+///
+/// # Returns
+///
+/// One meal per line with the cheapest one in bold, or `-` if there are none.
+fn format_field(row: &CategoryRow, canteen: usize, price_group: PriceGroup) -> String {
+    let mut value = String::new();
+    for (index, meal) in row.meals[canteen].iter().enumerate() {
+        let price = format_price(price_group.price(&meal.prices));
+        let line = if row.is_cheapest(canteen, index) {
+            format!("**★ {} — {}**\n", meal.name, price)
+        } else {
+            format!("• {} — {}\n", meal.name, price)
+        };
+        if value.len() + line.len() > FIELD_LIMIT {
+            break;
+        }
+        value.push_str(&line);
+    }
+
+    if value.is_empty() {
+        String::from("-")
+    } else {
+        value
+    }
+}
+
+/// Shortens a text to a maximum number of characters.
+///
+/// # Arguments
+///
+/// * `text` - The text to shorten.
+/// * `limit` - The maximum number of characters.
+///
+/// # Returns
+///
+/// The first `limit` characters of the text.
+fn truncate(text: &str, limit: usize) -> String {
+    text.chars().take(limit).collect()
+}
+
+/// Command to compare the menus of several canteens side by side.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `first` - The first canteen.
+/// * `second` - The second canteen.
+/// * `third` - An optional third canteen.
+/// * `fourth` - An optional fourth canteen.
+/// * `date` - Optional date string in `%Y-%m-%d` format, defaults to today.
+/// * `price_group` - Optional price group used to find the cheapest meals (defaults to students).
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+#[poise::command(
+    slash_command,
+    name_localized("de", "vergleich"),
+    description_localized("de", "Vergleicht die Speisepläne mehrerer Mensen.")
+)]
+pub async fn compare(
+    ctx: Context<'_>,
+    #[description = "First canteen (name or ID)"]
+    #[description_localized("de", "Erste Mensa (Name oder ID)")]
+    #[name_localized("de", "erste")]
+    #[autocomplete = "all_canteens"]
+    first: String,
+    #[description = "Second canteen (name or ID)"]
+    #[description_localized("de", "Zweite Mensa (Name oder ID)")]
+    #[name_localized("de", "zweite")]
+    #[autocomplete = "all_canteens"]
+    second: String,
+    #[description = "Third canteen (name or ID)"]
+    #[description_localized("de", "Dritte Mensa (Name oder ID)")]
+    #[name_localized("de", "dritte")]
+    #[autocomplete = "all_canteens"]
+    third: Option<String>,
+    #[description = "Fourth canteen (name or ID)"]
+    #[description_localized("de", "Vierte Mensa (Name oder ID)")]
+    #[name_localized("de", "vierte")]
+    #[autocomplete = "all_canteens"]
+    fourth: Option<String>,
+    #[description = "Choose a date"]
+    #[description_localized("de", "Wähle ein Datum")]
+    #[name_localized("de", "datum")]
+    date: Option<String>,
+    #[description = "Price group used to find the cheapest meals"]
+    #[description_localized("de", "Preisgruppe für die günstigsten Gerichte")]
+    #[name_localized("de", "preisgruppe")]
+    price_group: Option<PriceGroup>,
+) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+    let date = get_date(date, timezone(ctx));
+    let price_group = price_group.unwrap_or_default();
+
+    let mut names = vec![first, second];
+    names.extend(third);
+    names.extend(fourth);
+
+    ctx.defer().await?;
+
+    let mut canteens: Vec<openmensa_rust_interface::Canteen> = Vec::new();
+    for name in &names {
+        match canteen::find(name).await {
+            Ok(Some(can)) => {
+                if !canteens.iter().any(|canteen| canteen.id == can.id) {
+                    canteens.push(can);
+                }
+            }
+            Ok(None) => {
+                eprintln!("Canteen not found: {}", name);
+                ctx.say(format!("{} ({})", tr(lang, Message::CanteenNotFound), name))
+                    .await?;
+                return Ok(());
+            }
+            Err(err) => {
+                eprintln!("Error fetching canteen: {}", err);
+                ctx.say(tr(lang, Message::CanteenFetchFailed)).await?;
+                return Ok(());
+            }
+        }
+    }
+    if canteens.len() < 2 {
+        ctx.say(tr(lang, Message::TooFewCanteens)).await?;
+        return Ok(());
+    }
+
+    let comparison = match Comparison::fetch(&canteens, date, price_group).await {
+        Ok(comparison) => comparison,
+        Err(err) => {
+            eprintln!("Error fetching meals: {}", err);
+            ctx.say(tr(lang, Message::MealsFetchFailed)).await?;
+            return Ok(());
+        }
+    };
+
+    let header = format!(
+        "**{}** · {} · ★ {} {}",
+        tr(lang, Message::Comparison),
+        i18n::date(lang, date),
+        tr(lang, Message::CheapestFor),
+        price_group.label(lang)
+    );
+    if comparison.rows.is_empty() {
+        ctx.say(format!("{}\n{}", header, tr(lang, Message::NoMeals)))
+            .await?;
+        return Ok(());
+    }
+
+    // Discord limits the number and the total length of the embeds in a message,
+    // so long menus are split into follow-ups
+    let mut messages: Vec<Vec<CreateEmbed>> = vec![Vec::new()];
+    let mut length = 0;
+    for row in &comparison.rows {
+        let title = truncate(&row.category, NAME_LIMIT);
+        let mut embed_length = title.chars().count();
+        let mut embed = CreateEmbed::new().title(title);
+        for (index, canteen) in comparison.canteens.iter().enumerate() {
+            let name = truncate(canteen, NAME_LIMIT);
+            let value = format_field(row, index, comparison.price_group);
+            embed_length += name.chars().count() + value.chars().count();
+            embed = embed.field(name, value, true);
+        }
+
+        let current = messages.last_mut().unwrap();
+        if current.len() == EMBEDS_PER_MESSAGE || length + embed_length > MESSAGE_EMBED_LIMIT {
+            messages.push(vec![embed]);
+            length = embed_length;
+        } else {
+            current.push(embed);
+            length += embed_length;
+        }
+    }
+
+    for (index, embeds) in messages.into_iter().enumerate() {
+        let mut reply = CreateReply::default();
+        if index == 0 {
+            reply = reply.content(&header);
+        }
+        for embed in embeds {
+            reply = reply.embed(embed);
+        }
+        ctx.send(reply).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+
+use super::commands::{get_date, guild_canteens, reply_language, timezone, Context, Error};
+use crate::i18n::{self, tr, Message};
+use crate::models::menu::{self, ServedMeal};
+use crate::models::price::format_prices;
+
+/// Maximum number of fields in an embed.
+const MAX_FIELDS: usize = 25;
+
+/// Maximum length of an embed field name.
+const NAME_LIMIT: usize = 256;
+
+/// Maximum length of an embed field value.
+const FIELD_LIMIT: usize = 1024;
+
+/// Maximum total length of an embed.
+const EMBED_LIMIT: usize = 6000;
+
+/// Length reserved for the footer summarising the results that did not fit.
+const FOOTER_RESERVE: usize = 50;
+
+/// Command to find where a dish is served in the configured canteens.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `dish` - The text to look for in meal names.
+/// * `date` - Optional date string in `%Y-%m-%d` format, defaults to today.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+#[poise::command(
+    slash_command,
+    name_localized("de", "finden"),
+    description_localized(
+        "de",
+        "Sucht ein Gericht auf den Speiseplänen der konfigurierten Mensen."
+    )
+)]
+pub async fn find(
+    ctx: Context<'_>,
+    #[description = "Text to look for in meal names"]
+    #[description_localized("de", "Text, nach dem in Gerichten gesucht wird")]
+    #[name_localized("de", "gericht")]
+    #[max_length = 100]
+    dish: String,
+    #[description = "Choose a date"]
+    #[description_localized("de", "Wähle ein Datum")]
+    #[name_localized("de", "datum")]
+    date: Option<String>,
+) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+    let date = get_date(date, timezone(ctx));
+    let dish = dish.trim();
+    if dish.is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content(tr(lang, Message::EmptyQuery))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let Some(canteens) = guild_canteens(ctx, lang).await? else {
+        return Ok(());
+    };
+
+    let matches: Vec<ServedMeal> = menu::fetch_all(&canteens, date)
+        .await
+        .into_iter()
+        .filter(|served| menu::matches(&served.meal.name, dish))
+        .collect();
+
+    if matches.is_empty() {
+        ctx.say(format!(
+            "{} ({})",
+            tr(lang, Message::DishNotFound),
+            i18n::date(lang, date)
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let title = format!(
+        "{} \"{}\" · {}",
+        tr(lang, Message::MatchesFor),
+        dish,
+        i18n::date(lang, date)
+    );
+    let mut length = title.chars().count();
+    let mut embed = CreateEmbed::new().title(title);
+    let mut shown = 0;
+    for served in matches.iter().take(MAX_FIELDS) {
+        let mut value = format!(
+            "**{}**\n{}",
+            served.canteen,
+            format_prices(&served.meal.prices, lang)
+        );
+        if !served.meal.notes.is_empty() {
+            value.push('\n');
+            value.push_str(&served.meal.notes.join(", "));
+        }
+
+        let name: String = served.meal.name.chars().take(NAME_LIMIT).collect();
+        let value: String = value.chars().take(FIELD_LIMIT).collect();
+        // Discord rejects embeds longer than 6000 characters in total
+        length += name.chars().count() + value.chars().count();
+        if length > EMBED_LIMIT - FOOTER_RESERVE {
+            break;
+        }
+        embed = embed.field(name, value, false);
+        shown += 1;
+    }
+    if matches.len() > shown {
+        embed = embed.footer(CreateEmbedFooter::new(format!(
+            "+{} {}",
+            matches.len() - shown,
+            tr(lang, Message::MoreResults)
+        )));
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
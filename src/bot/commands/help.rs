@@ -0,0 +1,547 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+use std::collections::HashMap;
+
+use super::commands::{reply_language, Context, Data, Error};
+use crate::i18n::{tr, Language, Message};
+
+/// Usage examples shown by the help command, keyed by the qualified command name.
+///
+/// Slash command examples use the default names and are translated when shown,
+/// prefix command examples are shown as they are.
+const EXAMPLES: &[(&str, &[&str])] = &[
+    (
+        "meal",
+        &[
+            "/meal canteen:Mensa Nord",
+            "/meal canteen:Mensa Nord date:2024-07-01",
+        ],
+    ),
+    (
+        "rate",
+        &[
+            "/rate canteen:Mensa Nord meal:Spaghetti Bolognese rating:4",
+            "/rate canteen:Mensa Nord meal:Chili sin Carne rating:2 comment:🧂🧂🧂",
+        ],
+    ),
+    (
+        "alerts add",
+        &[
+            "/alerts add pattern:Schnitzel",
+            "/alerts add pattern:^(Pizza|Pasta) canteen:Mensa Nord regex:True",
+        ],
+    ),
+    ("alerts list", &["/alerts list"]),
+    ("alerts remove", &["/alerts remove pattern:Schnitzel"]),
+    ("canteen", &["/canteen name:Mensa Nord"]),
+    (
+        "compare",
+        &[
+            "/compare first:Mensa Nord second:Mensa Süd",
+            "/compare first:Mensa Nord second:Mensa Süd third:Cafeteria price_group:Employees",
+        ],
+    ),
+    (
+        "find",
+        &["/find dish:Käse", "/find dish:Schnitzel date:2024-07-01"],
+    ),
+    (
+        "cheapest",
+        &[
+            "/cheapest",
+            "/cheapest price_group:Employees count:10 full_meals_only:True",
+        ],
+    ),
+    ("lunch", &["/lunch canteen:Mensa Nord time:12:15"]),
+    (
+        "meme random",
+        &["/meme random", "/meme random subreddit:ProgrammerHumor"],
+    ),
+    ("meme top", &["/meme top"]),
+    ("daily_fact", &["/daily_fact", "/daily_fact language:en"]),
+    (
+        "random_fact",
+        &[
+            "/random_fact",
+            "/random_fact language:de",
+            "/random_fact private:True",
+        ],
+    ),
+    (
+        "trivia play",
+        &[
+            "/trivia play",
+            "/trivia play mode:True or false language:de",
+        ],
+    ),
+    ("trivia leaderboard", &["/trivia leaderboard"]),
+    ("bot", &["/bot"]),
+    ("help", &["/help", "/help command:meal", "~help meal"]),
+];
+
+/// Picks the translation of a name or description.
+///
+/// # Arguments
+///
+/// * `text` - The default text.
+/// * `localizations` - The translations, keyed by Discord locale.
+/// * `lang` - The language to translate into.
+///
+/// # Returns
+///
+/// The translation, or the default text if there is none.
+fn localized<'a>(
+    text: &'a str,
+    localizations: &'a HashMap<String, String>,
+    lang: Language,
+) -> &'a str {
+    localizations.get(lang.code()).map_or(text, String::as_str)
+}
+
+/// Looks up a command by its qualified name, including subcommands.
+///
+/// # Arguments
+///
+/// * `commands` - The commands to search through.
+/// * `name` - The qualified name of the command, e.g. `meal`, in English or translated.
+/// * `lang` - The language of translated names.
+///
+/// # Returns
+///
+/// The command and its parent commands, outermost first, or `None` if there is none.
+fn find_command<'a>(
+    commands: &'a [poise::Command<Data, Error>],
+    name: &str,
+    lang: Language,
+) -> Option<Vec<&'a poise::Command<Data, Error>>> {
+    fn search<'a>(
+        commands: &'a [poise::Command<Data, Error>],
+        name: &str,
+        lang: Language,
+        parents: &[&'a poise::Command<Data, Error>],
+    ) -> Option<Vec<&'a poise::Command<Data, Error>>> {
+        commands.iter().find_map(|command| {
+            let mut path = parents.to_vec();
+            path.push(command);
+            if command.qualified_name == name || display_name(&path, lang) == name {
+                Some(path)
+            } else {
+                search(&command.subcommands, name, lang, &path)
+            }
+        })
+    }
+
+    search(commands, name, lang, &[])
+}
+
+/// Builds the translated qualified name of a command.
+///
+/// # Arguments
+///
+/// * `path` - The command and its parent commands, outermost first.
+/// * `lang` - The language to translate into.
+///
+/// # Returns
+///
+/// The names of the commands separated by spaces.
+fn display_name(path: &[&poise::Command<Data, Error>], lang: Language) -> String {
+    path.iter()
+        .map(|command| localized(&command.name, &command.name_localizations, lang))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Translates the command, parameter and choice names of a slash command example.
+///
+/// # Arguments
+///
+/// * `commands` - The commands registered in the framework.
+/// * `example` - The example, using the default names.
+/// * `lang` - The language to translate into.
+///
+/// # Returns
+///
+/// The translated example, or the example as it is if it is no slash command.
+fn localize_example(
+    commands: &[poise::Command<Data, Error>],
+    example: &str,
+    lang: Language,
+) -> String {
+    let Some(rest) = example.strip_prefix('/') else {
+        return example.to_string();
+    };
+    let words: Vec<&str> = rest.split(' ').collect();
+    // The longest run of leading words that names a command, e.g. `alerts add`
+    let Some((path, count)) = (1..=words.len()).rev().find_map(|count| {
+        Some((
+            find_command(commands, &words[..count].join(" "), lang)?,
+            count,
+        ))
+    }) else {
+        return example.to_string();
+    };
+    let command = path[path.len() - 1];
+
+    // Values may contain spaces, so words that start no parameter belong to the previous one
+    let mut arguments: Vec<(&poise::CommandParameter<Data, Error>, String)> = Vec::new();
+    for word in &words[count..] {
+        let parameter = word.split_once(':').and_then(|(name, value)| {
+            let parameter = command
+                .parameters
+                .iter()
+                .find(|parameter| parameter.name == name)?;
+            Some((parameter, value))
+        });
+        match (parameter, arguments.last_mut()) {
+            (Some((parameter, value)), _) => arguments.push((parameter, value.to_string())),
+            (None, Some((_, value))) => {
+                value.push(' ');
+                value.push_str(word);
+            }
+            (None, None) => return example.to_string(),
+        }
+    }
+
+    let mut parts = vec![format!("/{}", display_name(&path, lang))];
+    for (parameter, value) in &arguments {
+        let value = parameter
+            .choices
+            .iter()
+            .find(|choice| choice.name == *value)
+            .map_or(value.as_str(), |choice| {
+                localized(&choice.name, &choice.localizations, lang)
+            });
+        parts.push(format!(
+            "{}:{}",
+            localized(&parameter.name, &parameter.name_localizations, lang),
+            value
+        ));
+    }
+    parts.join(" ")
+}
+
+/// Formats the cooldowns of a command for display.
+///
+/// # Arguments
+///
+/// * `command` - The command whose cooldowns are formatted.
+/// * `lang` - The language of the scopes.
+///
+/// # Returns
+///
+/// One line per configured cooldown, or `None` if the command has no cooldown.
+fn format_cooldowns(command: &poise::Command<Data, Error>, lang: Language) -> Option<String> {
+    let config = command.cooldown_config.read().unwrap();
+    let cooldowns: Vec<String> = [
+        (Message::CooldownGlobal, config.global),
+        (Message::CooldownPerUser, config.user),
+        (Message::CooldownPerGuild, config.guild),
+        (Message::CooldownPerChannel, config.channel),
+        (Message::CooldownPerMember, config.member),
+    ]
+    .into_iter()
+    .filter_map(|(scope, duration)| {
+        duration.map(|duration| format!("{}s {}", duration.as_secs(), tr(lang, scope)))
+    })
+    .collect();
+
+    if cooldowns.is_empty() {
+        None
+    } else {
+        Some(cooldowns.join("\n"))
+    }
+}
+
+/// Formats the restrictions of a command, like required permissions or owner-only access.
+///
+/// # Arguments
+///
+/// * `command` - The command whose restrictions are formatted.
+/// * `lang` - The language of the restrictions.
+///
+/// # Returns
+///
+/// One line per restriction, or `None` if anyone can use the command.
+fn format_restrictions(command: &poise::Command<Data, Error>, lang: Language) -> Option<String> {
+    let mut restrictions = Vec::new();
+
+    let permissions = command.required_permissions | command.default_member_permissions;
+    if !permissions.is_empty() {
+        restrictions.push(format!(
+            "{}: {}",
+            tr(lang, Message::Permissions),
+            permissions
+        ));
+    }
+    for (restricted, message) in [
+        (command.owners_only, Message::OwnersOnly),
+        (command.guild_only, Message::ServersOnly),
+        (command.dm_only, Message::DirectMessagesOnly),
+        (command.nsfw_only, Message::NsfwChannelsOnly),
+    ] {
+        if restricted {
+            restrictions.push(String::from(tr(lang, message)));
+        }
+    }
+
+    if restrictions.is_empty() {
+        None
+    } else {
+        Some(restrictions.join("\n"))
+    }
+}
+
+/// Builds an embed describing a single command.
+///
+/// # Arguments
+///
+/// * `commands` - The commands registered in the framework.
+/// * `path` - The command to describe and its parent commands, outermost first.
+/// * `lang` - The language of the embed.
+///
+/// # Returns
+///
+/// An embed with the description, parameters, examples, cooldowns and restrictions of the command.
+fn command_embed(
+    commands: &[poise::Command<Data, Error>],
+    path: &[&poise::Command<Data, Error>],
+    lang: Language,
+) -> CreateEmbed {
+    let command = path[path.len() - 1];
+    let description = match &command.description {
+        Some(description) => localized(description, &command.description_localizations, lang),
+        None => tr(lang, Message::NoDescription),
+    };
+    let mut embed = CreateEmbed::new()
+        .title(format!("/{}", display_name(path, lang)))
+        .description(description);
+
+    if !command.parameters.is_empty() {
+        let parameters: Vec<String> = command
+            .parameters
+            .iter()
+            .map(|parameter| {
+                format!(
+                    "`{}`{} — {}",
+                    localized(&parameter.name, &parameter.name_localizations, lang),
+                    if parameter.required {
+                        String::new()
+                    } else {
+                        format!(" ({})", tr(lang, Message::Optional))
+                    },
+                    parameter.description.as_deref().map_or("-", |description| {
+                        localized(description, &parameter.description_localizations, lang)
+                    })
+                )
+            })
+            .collect();
+        embed = embed.field(tr(lang, Message::Parameters), parameters.join("\n"), false);
+    }
+
+    if !command.subcommands.is_empty() {
+        let subcommands: Vec<String> = command
+            .subcommands
+            .iter()
+            .filter(|subcommand| !subcommand.hide_in_help)
+            .map(|subcommand| {
+                let mut path = path.to_vec();
+                path.push(subcommand);
+                format!("`/{}`", display_name(&path, lang))
+            })
+            .collect();
+        embed = embed.field(
+            tr(lang, Message::Subcommands),
+            subcommands.join("\n"),
+            false,
+        );
+    }
+
+    if let Some((_, examples)) = EXAMPLES
+        .iter()
+        .find(|(name, _)| *name == command.qualified_name)
+    {
+        let examples: Vec<String> = examples
+            .iter()
+            .map(|example| format!("`{}`", localize_example(commands, example, lang)))
+            .collect();
+        embed = embed.field(tr(lang, Message::Examples), examples.join("\n"), false);
+    }
+
+    if let Some(cooldowns) = format_cooldowns(command, lang) {
+        embed = embed.field(tr(lang, Message::Cooldown), cooldowns, true);
+    }
+
+    if let Some(restrictions) = format_restrictions(command, lang) {
+        embed = embed.field(tr(lang, Message::Restrictions), restrictions, true);
+    }
+
+    embed
+}
+
+/// Builds an embed listing all visible commands.
+///
+/// # Arguments
+///
+/// * `commands` - The commands registered in the framework.
+/// * `lang` - The language of the embed.
+///
+/// # Returns
+///
+/// An embed with one line per command and its description.
+fn overview_embed(commands: &[poise::Command<Data, Error>], lang: Language) -> CreateEmbed {
+    let lines: Vec<String> = commands
+        .iter()
+        .filter(|command| !command.hide_in_help)
+        .map(|command| {
+            format!(
+                "`/{}` — {}",
+                localized(&command.name, &command.name_localizations, lang),
+                command.description.as_deref().map_or("-", |description| {
+                    localized(description, &command.description_localizations, lang)
+                })
+            )
+        })
+        .collect();
+
+    CreateEmbed::new()
+        .title(tr(lang, Message::Commands))
+        .description(lines.join("\n"))
+        .footer(CreateEmbedFooter::new(tr(lang, Message::HelpFooter)))
+}
+
+/// Autocompletes the names of all visible commands, including subcommands.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `partial` - The partial command name typed so far.
+///
+/// # Returns
+///
+/// The qualified names of matching commands.
+async fn autocomplete_command(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    fn collect(commands: &[poise::Command<Data, Error>], partial: &str, names: &mut Vec<String>) {
+        for command in commands.iter().filter(|command| !command.hide_in_help) {
+            if command.qualified_name.starts_with(partial) {
+                names.push(command.qualified_name.clone());
+            }
+            collect(&command.subcommands, partial, names);
+        }
+    }
+
+    let mut names = Vec::new();
+    collect(&ctx.framework().options().commands, partial, &mut names);
+    names
+}
+
+/// Command to show all commands or details about a single command.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `command` - Optional name of the command to show details for.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    track_edits,
+    name_localized("de", "hilfe"),
+    description_localized("de", "Zeigt alle Befehle oder Details zu einem Befehl an.")
+)]
+pub async fn help(
+    ctx: Context<'_>,
+    #[description = "Show details for this command"]
+    #[description_localized("de", "Details zu diesem Befehl anzeigen")]
+    #[name_localized("de", "befehl")]
+    #[autocomplete = "autocomplete_command"]
+    #[rest]
+    command: Option<String>,
+) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+    let commands = &ctx.framework().options().commands;
+
+    let embed = match command {
+        Some(name) => {
+            let name = name.trim().trim_start_matches(['/', '~']);
+            match find_command(commands, name, lang)
+                .filter(|path| path.iter().all(|command| !command.hide_in_help))
+            {
+                Some(path) => command_embed(commands, &path, lang),
+                None => {
+                    ctx.say(format!("{} `{}`.", tr(lang, Message::UnknownCommand), name))
+                        .await?;
+                    return Ok(());
+                }
+            }
+        }
+        None => overview_embed(commands, lang),
+    };
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::commands::{alerts, compare, trivia};
+
+    /// Builds the commands with their qualified names filled in, as the framework does.
+    fn commands() -> Vec<poise::Command<Data, Error>> {
+        let mut commands = vec![alerts(), compare(), trivia(), help()];
+        poise::framework::set_qualified_names(&mut commands);
+        commands
+    }
+
+    #[test]
+    fn examples_are_translated() {
+        let commands = commands();
+
+        assert_eq!(
+            localize_example(
+                &commands,
+                "/alerts add pattern:^(Pizza|Pasta) canteen:Mensa Nord regex:True",
+                Language::De
+            ),
+            "/alarme hinzufügen muster:^(Pizza|Pasta) mensa:Mensa Nord regex:True"
+        );
+        assert_eq!(
+            localize_example(
+                &commands,
+                "/compare first:Mensa Nord second:Mensa Süd price_group:Employees",
+                Language::De
+            ),
+            "/vergleich erste:Mensa Nord zweite:Mensa Süd preisgruppe:Bedienstete"
+        );
+        assert_eq!(
+            localize_example(&commands, "/trivia play mode:True or false", Language::De),
+            "/trivia spielen modus:Wahr oder falsch"
+        );
+    }
+
+    #[test]
+    fn examples_keep_default_names_in_english_and_for_prefix_commands() {
+        let commands = commands();
+        let example = "/alerts add pattern:Schnitzel";
+
+        assert_eq!(localize_example(&commands, example, Language::En), example);
+        assert_eq!(
+            localize_example(&commands, "~help meal", Language::De),
+            "~help meal"
+        );
+    }
+
+    #[test]
+    fn commands_are_found_by_translated_names() {
+        let commands = commands();
+
+        let path = find_command(&commands, "alarme liste", Language::De).unwrap();
+
+        assert_eq!(path.last().unwrap().qualified_name, "alerts list");
+        assert!(find_command(&commands, "alarme liste", Language::En).is_none());
+    }
+}
@@ -0,0 +1,353 @@
+use chrono::{NaiveDateTime, NaiveTime, TimeDelta};
+use openmensa_rust_interface::{get_canteen_by_name, get_meals};
+use poise::serenity_prelude::{self as serenity, Mentionable};
+use poise::CreateReply;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+
+use super::commands::{all_canteens, reply_language, timezone, Context, Error};
+use crate::clock;
+use crate::i18n::{tr, Language, Message};
+
+/// How long before the meetup the attendees are reminded.
+const REMINDER_BEFORE: TimeDelta = TimeDelta::minutes(10);
+
+/// Maximum length of an embed field value.
+const FIELD_LIMIT: usize = 1024;
+
+/// Maximum length of a message.
+const MESSAGE_LIMIT: usize = 2000;
+
+/// Lists attendees as mentions, split into chunks that fit a length limit.
+///
+/// # Arguments
+///
+/// * `attendees` - The users who joined.
+/// * `limit` - The maximum length of a chunk.
+///
+/// # Returns
+///
+/// The mentions separated by spaces, one string per chunk.
+fn mentions(attendees: &[serenity::UserId], limit: usize) -> Vec<String> {
+    let mut chunks = vec![String::new()];
+    for user in attendees {
+        let mention = user.mention().to_string();
+        let chunk = chunks.last_mut().unwrap();
+        if chunk.is_empty() {
+            chunk.push_str(&mention);
+        } else if chunk.len() + 1 + mention.len() <= limit {
+            chunk.push(' ');
+            chunk.push_str(&mention);
+        } else {
+            chunks.push(mention);
+        }
+    }
+    chunks
+}
+
+/// Lists attendees for the sign-up embed.
+///
+/// Attendees that do not fit into an embed field are summarised as `+N`.
+///
+/// # Arguments
+///
+/// * `attendees` - The users who joined.
+/// * `lang` - The language of the placeholder.
+///
+/// # Returns
+///
+/// The mentions, or a placeholder if nobody joined.
+fn attendee_list(attendees: &[serenity::UserId], lang: Language) -> String {
+    if attendees.is_empty() {
+        return String::from(tr(lang, Message::NoAttendees));
+    }
+
+    // Leaves room for the summary of the attendees that do not fit
+    let chunks = mentions(attendees, FIELD_LIMIT - 8);
+    let shown = chunks[0].split(' ').count();
+    if shown < attendees.len() {
+        format!("{} +{}", chunks[0], attendees.len() - shown)
+    } else {
+        chunks[0].clone()
+    }
+}
+
+/// Builds the sign-up embed of a meetup.
+///
+/// # Arguments
+///
+/// * `canteen` - The name of the canteen.
+/// * `time` - The time of the meetup.
+/// * `menu` - Today's meals of the canteen, one per line.
+/// * `attendees` - The users who joined.
+/// * `closed` - Whether the sign-up is closed.
+/// * `lang` - The language of the embed.
+///
+/// # Returns
+///
+/// An embed with the attendee list and the menu.
+fn lunch_embed(
+    canteen: &str,
+    time: NaiveTime,
+    menu: &str,
+    attendees: &[serenity::UserId],
+    closed: bool,
+    lang: Language,
+) -> CreateEmbed {
+    // Discord rejects empty field values
+    let menu = if menu.trim().is_empty() {
+        tr(lang, Message::NoMeals)
+    } else {
+        menu
+    };
+
+    let embed = CreateEmbed::new()
+        .title(format!(
+            "{} {} · {}",
+            tr(lang, Message::LunchAt),
+            canteen,
+            time.format("%H:%M")
+        ))
+        .field(
+            format!("{} ({})", tr(lang, Message::Attendees), attendees.len()),
+            attendee_list(attendees, lang),
+            false,
+        )
+        .field(tr(lang, Message::Menu), menu, false);
+
+    if closed {
+        embed.footer(CreateEmbedFooter::new(tr(lang, Message::LunchClosed)))
+    } else {
+        embed
+    }
+}
+
+/// Creates the join and leave buttons of a meetup.
+///
+/// # Arguments
+///
+/// * `ctx_id` - The ID of the invocation, used as prefix for the button IDs.
+/// * `lang` - The language of the button labels.
+///
+/// # Returns
+///
+/// A row with a join and a leave button.
+fn lunch_buttons(ctx_id: u64, lang: Language) -> serenity::CreateActionRow {
+    serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(format!("{}join", ctx_id))
+            .label(tr(lang, Message::Join))
+            .style(serenity::ButtonStyle::Success),
+        serenity::CreateButton::new(format!("{}leave", ctx_id))
+            .label(tr(lang, Message::Leave))
+            .style(serenity::ButtonStyle::Secondary),
+    ])
+}
+
+/// Formats today's menu of a canteen for the sign-up embed.
+///
+/// # Arguments
+///
+/// * `canteen` - The canteen to fetch the menu for.
+/// * `date` - The date of the meetup in `%Y-%m-%d` format.
+/// * `lang` - The language of the fallback texts.
+///
+/// # Returns
+///
+/// One meal per line, shortened to fit into an embed field.
+async fn menu(canteen: &openmensa_rust_interface::Canteen, date: &str, lang: Language) -> String {
+    let meals = match get_meals(canteen, date).await {
+        Ok(meals) if !meals.is_empty() => meals,
+        Ok(_) => return String::from(tr(lang, Message::NoMeals)),
+        Err(err) => {
+            eprintln!("Error fetching meals: {:?}", err);
+            return String::from(tr(lang, Message::MealsFetchFailed));
+        }
+    };
+
+    let mut menu = String::new();
+    for meal in meals {
+        let line = format!("• {}\n", meal.name);
+        if menu.len() + line.len() > FIELD_LIMIT {
+            break;
+        }
+        menu.push_str(&line);
+    }
+    menu
+}
+
+/// Command to organise a lunch meetup at a canteen.
+///
+/// Posts a sign-up message with today's menu. Attendees are pinged shortly before
+/// the meetup and the sign-up closes when it starts. Meetups are only kept in memory,
+/// so a restart of the bot stops the sign-up and the reminder.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `canteen` - The name of the canteen to meet at.
+/// * `time` - The time of the meetup in `HH:MM` format.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+#[poise::command(
+    slash_command,
+    guild_only,
+    name_localized("de", "mittagessen"),
+    description_localized("de", "Verabredet euch zum Mittagessen in der Mensa.")
+)]
+pub async fn lunch(
+    ctx: Context<'_>,
+    #[description = "choose a canteen"]
+    #[description_localized("de", "Wähle eine Mensa")]
+    #[name_localized("de", "mensa")]
+    #[autocomplete = "all_canteens"]
+    canteen: String,
+    #[description = "Time of the meetup, e.g. 12:15"]
+    #[description_localized("de", "Uhrzeit des Treffens, z. B. 12:15")]
+    #[name_localized("de", "uhrzeit")]
+    time: String,
+) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+
+    let Ok(time) = NaiveTime::parse_from_str(time.trim(), "%H:%M") else {
+        ctx.send(
+            CreateReply::default()
+                .content(tr(lang, Message::InvalidTime))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let timezone = timezone(ctx);
+    let now = clock::now(timezone);
+    let meetup_at: NaiveDateTime = now.date().and_time(time);
+    if meetup_at <= now {
+        ctx.send(
+            CreateReply::default()
+                .content(tr(lang, Message::TimeInPast))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let canteen = match get_canteen_by_name(&canteen).await {
+        Ok(Some(can)) => can,
+        Ok(None) => {
+            eprintln!("Canteen not found: {}", canteen);
+            ctx.say(tr(lang, Message::CanteenNotFound)).await?;
+            return Ok(());
+        }
+        Err(err) => {
+            eprintln!("Error fetching canteen by name: {:?}", err);
+            ctx.say(tr(lang, Message::CanteenFetchFailed)).await?;
+            return Ok(());
+        }
+    };
+
+    let menu = menu(&canteen, &now.date().to_string(), lang).await;
+    let mut attendees = vec![ctx.author().id];
+
+    let ctx_id = ctx.id();
+    let message_id = ctx
+        .send(
+            CreateReply::default()
+                .embed(lunch_embed(
+                    &canteen.name,
+                    time,
+                    &menu,
+                    &attendees,
+                    false,
+                    lang,
+                ))
+                .components(vec![lunch_buttons(ctx_id, lang)]),
+        )
+        .await?
+        .message()
+        .await?
+        .id;
+
+    // Interaction tokens expire after 15 minutes, so later messages go through the channel
+    let channel_id = ctx.channel_id();
+
+    // A meetup that is close already gets no separate reminder
+    let reminder_at = meetup_at - REMINDER_BEFORE;
+    let mut reminded = now >= reminder_at;
+
+    loop {
+        let now = clock::now(timezone);
+        if !reminded && now >= reminder_at {
+            reminded = true;
+            if !attendees.is_empty() {
+                let reminder = format!(
+                    "{} {} · {}",
+                    tr(lang, Message::LunchReminder),
+                    canteen.name,
+                    time.format("%H:%M")
+                );
+                // Every attendee is pinged, even if the mentions need several messages
+                for chunk in mentions(&attendees, MESSAGE_LIMIT - reminder.len() - 1) {
+                    channel_id
+                        .say(ctx, format!("{} {}", chunk, reminder))
+                        .await?;
+                }
+            }
+        }
+        if now >= meetup_at {
+            break;
+        }
+
+        let next = if reminded { meetup_at } else { reminder_at };
+        let Some(press) = serenity::ComponentInteractionCollector::new(ctx)
+            .filter(move |press| press.data.custom_id.starts_with(&ctx_id.to_string()))
+            .timeout((next - now).to_std().unwrap_or_default())
+            .await
+        else {
+            continue;
+        };
+
+        let user = press.user.id;
+        if press.data.custom_id.ends_with("join") {
+            if !attendees.contains(&user) {
+                attendees.push(user);
+            }
+        } else {
+            attendees.retain(|attendee| *attendee != user);
+        }
+
+        press
+            .create_response(
+                ctx,
+                serenity::CreateInteractionResponse::UpdateMessage(
+                    serenity::CreateInteractionResponseMessage::new().embed(lunch_embed(
+                        &canteen.name,
+                        time,
+                        &menu,
+                        &attendees,
+                        false,
+                        lang,
+                    )),
+                ),
+            )
+            .await?;
+    }
+
+    channel_id
+        .edit_message(
+            ctx,
+            message_id,
+            serenity::EditMessage::new()
+                .embed(lunch_embed(
+                    &canteen.name,
+                    time,
+                    &menu,
+                    &attendees,
+                    true,
+                    lang,
+                ))
+                .components(vec![]),
+        )
+        .await?;
+    Ok(())
+}
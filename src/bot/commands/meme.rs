@@ -0,0 +1,579 @@
+use chrono::Utc;
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serde::{Deserialize, Serialize};
+use serenity::builder::CreateEmbed;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use super::commands::{reply_language, Context, Data, Error};
+use crate::apis::meme_api::{self, Meme};
+use crate::bot::embeds::meme_embed;
+use crate::config::{ContentPolicy, MemesConfig};
+use crate::i18n::{tr, Message};
+
+/// Number of batches fetched before giving up on finding a meme that passes the filters.
+const MAX_ATTEMPTS: usize = 3;
+
+/// Number of memes prefetched into the shared pool at once.
+const POOL_SIZE: u32 = 30;
+
+/// Pool size below which the pool is refilled in the background.
+const POOL_LOW: usize = 10;
+
+/// Number of memes fetched at once for a specific subreddit.
+const SUBREDDIT_BATCH_SIZE: u32 = 10;
+
+/// Number of posted meme URLs remembered per guild to avoid duplicates.
+const HISTORY_SIZE: usize = 500;
+
+/// Prefix of the custom IDs of the vote buttons.
+pub const VOTE_PREFIX: &str = "meme-vote:";
+
+/// Seconds a posted meme is kept for the leaderboard.
+const RETENTION: i64 = 7 * 24 * 60 * 60;
+
+/// Number of memes shown by the top command.
+const TOP_SIZE: usize = 10;
+
+/// A meme posted by the bot and the votes it received.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostedMeme {
+    /// The ID of the guild it was posted in, `None` for direct messages.
+    pub guild: Option<u64>,
+    /// The title of the meme.
+    pub title: String,
+    /// The link to the Reddit post of the meme.
+    pub post_link: String,
+    /// The subreddit the meme was posted in.
+    pub subreddit: String,
+    /// Unix timestamp of when the meme was posted.
+    pub posted_at: i64,
+    /// The votes of each user ID, `1` for up and `-1` for down.
+    pub votes: HashMap<String, i8>,
+}
+
+impl PostedMeme {
+    /// Returns the sum of all votes.
+    fn score(&self) -> i32 {
+        self.votes.values().map(|vote| i32::from(*vote)).sum()
+    }
+
+    /// Counts the votes of one kind.
+    fn count(&self, vote: i8) -> usize {
+        self.votes.values().filter(|value| **value == vote).count()
+    }
+
+    /// Checks whether the meme is older than the retention period.
+    fn is_expired(&self, now: i64) -> bool {
+        now - self.posted_at > RETENTION
+    }
+}
+
+/// What to do with a fetched meme.
+#[derive(Debug, PartialEq)]
+enum Verdict {
+    /// Post the meme, hidden behind spoiler formatting if `true`.
+    Post(bool),
+    /// Drop the meme and fetch another one.
+    Refetch,
+}
+
+/// Applies a content policy to a flagged meme.
+///
+/// # Arguments
+///
+/// * `policy` - The policy for the flag.
+/// * `nsfw_channel` - Whether the meme would be posted in an NSFW channel.
+///
+/// # Returns
+///
+/// Whether the meme can be posted and if it must be hidden.
+fn apply_policy(policy: ContentPolicy, nsfw_channel: bool) -> Verdict {
+    match policy {
+        ContentPolicy::Allow => Verdict::Post(false),
+        ContentPolicy::NsfwChannels if nsfw_channel => Verdict::Post(false),
+        ContentPolicy::NsfwChannels | ContentPolicy::Refetch => Verdict::Refetch,
+        ContentPolicy::Spoiler => Verdict::Post(true),
+    }
+}
+
+/// Checks a meme against the configured filters.
+///
+/// # Arguments
+///
+/// * `meme` - The fetched meme.
+/// * `filters` - The meme filters of the guild.
+/// * `nsfw_channel` - Whether the meme would be posted in an NSFW channel.
+///
+/// # Returns
+///
+/// Whether the meme can be posted and if it must be hidden.
+fn judge(meme: &Meme, filters: &MemesConfig, nsfw_channel: bool) -> Verdict {
+    if !filters.allows_subreddit(&meme.subreddit) {
+        return Verdict::Refetch;
+    }
+
+    let mut hidden = false;
+    for (flagged, policy) in [(meme.nsfw, filters.nsfw), (meme.spoiler, filters.spoiler)] {
+        if !flagged {
+            continue;
+        }
+        match apply_policy(policy, nsfw_channel) {
+            Verdict::Post(spoiler) => hidden |= spoiler,
+            Verdict::Refetch => return Verdict::Refetch,
+        }
+    }
+
+    Verdict::Post(hidden)
+}
+
+/// Checks whether the command was invoked in an age-restricted channel.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+///
+/// # Returns
+///
+/// `true` for NSFW guild channels, `false` everywhere else including direct messages.
+async fn is_nsfw_channel(ctx: Context<'_>) -> bool {
+    ctx.guild_channel()
+        .await
+        .is_some_and(|channel| channel.nsfw)
+}
+
+/// Takes a batch of memes to pick from.
+///
+/// Memes from the default subreddits come from the shared pool, which is filled with a
+/// single batch request when it is empty. Requests for a subreddit fetch their own batch.
+///
+/// # Arguments
+///
+/// * `data` - The shared bot state.
+/// * `subreddit` - Optional subreddit to fetch the memes from.
+///
+/// # Returns
+///
+/// The memes, or an error if they could not be fetched.
+async fn take_batch(data: &Data, subreddit: Option<&str>) -> Result<Vec<Meme>, meme_api::Error> {
+    if subreddit.is_some() {
+        return meme_api::batch(subreddit, SUBREDDIT_BATCH_SIZE).await;
+    }
+
+    let mut pool = data.meme_pool.lock().await;
+    if pool.is_empty() {
+        *pool = meme_api::batch(None, POOL_SIZE).await?;
+    }
+    Ok(std::mem::take(&mut *pool))
+}
+
+/// Puts unused memes back into the shared pool and refills it in the background when it runs low.
+///
+/// # Arguments
+///
+/// * `data` - The shared bot state.
+/// * `memes` - The memes that were not posted.
+async fn return_to_pool(data: &Data, memes: Vec<Meme>) {
+    let mut pool = data.meme_pool.lock().await;
+    pool.extend(memes);
+
+    if pool.len() < POOL_LOW {
+        let pool = data.meme_pool.clone();
+        tokio::spawn(async move {
+            match meme_api::batch(None, POOL_SIZE).await {
+                Ok(memes) => pool.lock().await.extend(memes),
+                Err(err) => eprintln!("Error prefetching memes: {:?}", err),
+            }
+        });
+    }
+}
+
+/// Posts a meme with vote buttons and remembers it for votes and deduplication.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `meme` - The meme to post.
+/// * `hidden` - Whether the meme must be hidden behind a spoiler.
+/// * `history_key` - The key of the meme history to add the meme to.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+async fn post_meme(
+    ctx: Context<'_>,
+    meme: &Meme,
+    hidden: bool,
+    history_key: String,
+) -> Result<(), Error> {
+    let posted = PostedMeme {
+        guild: ctx.guild_id().map(|guild_id| guild_id.get()),
+        title: meme.title.clone(),
+        post_link: meme.post_link.clone(),
+        subreddit: meme.subreddit.clone(),
+        posted_at: Utc::now().timestamp(),
+        votes: HashMap::new(),
+    };
+    let mut reply = CreateReply::default()
+        .embed(meme_embed(meme, hidden, reply_language(ctx)))
+        .components(vec![vote_buttons(&posted)]);
+    if hidden {
+        // Embed images can not be hidden, so the image is posted as a spoilered link instead
+        reply = reply.content(format!("|| {} ||", meme.url));
+    }
+
+    let message = ctx.send(reply).await?.message().await?.id;
+    ctx.data()
+        .votes
+        .update(|memes| {
+            memes.retain(|_, meme| !meme.is_expired(posted.posted_at));
+            memes.insert(message.to_string(), posted);
+        })
+        .await;
+    ctx.data()
+        .meme_history
+        .update(|history| {
+            let urls = history.entry(history_key).or_default();
+            urls.push(meme.url.clone());
+            if urls.len() > HISTORY_SIZE {
+                urls.remove(0);
+            }
+        })
+        .await;
+    Ok(())
+}
+
+/// Builds the vote buttons of a posted meme, labelled with the current vote counts.
+///
+/// # Arguments
+///
+/// * `meme` - The posted meme.
+///
+/// # Returns
+///
+/// A row with an upvote and a downvote button.
+fn vote_buttons(meme: &PostedMeme) -> serenity::CreateActionRow {
+    serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(format!("{}up", VOTE_PREFIX))
+            .label(meme.count(1).to_string())
+            .emoji('👍')
+            .style(serenity::ButtonStyle::Secondary),
+        serenity::CreateButton::new(format!("{}down", VOTE_PREFIX))
+            .label(meme.count(-1).to_string())
+            .emoji('👎')
+            .style(serenity::ButtonStyle::Secondary),
+    ])
+}
+
+/// Records a vote from one of the buttons below a posted meme.
+///
+/// The buttons stay usable after restarts, so votes are handled by the event handler
+/// instead of a collector.
+///
+/// # Arguments
+///
+/// * `ctx` - The serenity context.
+/// * `press` - The button interaction.
+/// * `data` - The shared bot state.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if responding fails.
+pub async fn handle_vote(
+    ctx: &serenity::Context,
+    press: &serenity::ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let vote = match press.data.custom_id.strip_prefix(VOTE_PREFIX) {
+        Some("up") => 1,
+        Some("down") => -1,
+        _ => return Ok(()),
+    };
+    let user = press.user.id.to_string();
+
+    let buttons = data
+        .votes
+        .update(|memes| {
+            memes.get_mut(&press.message.id.to_string()).map(|meme| {
+                // Pressing the same button again takes the vote back
+                if meme.votes.get(&user) == Some(&vote) {
+                    meme.votes.remove(&user);
+                } else {
+                    meme.votes.insert(user, vote);
+                }
+                vote_buttons(meme)
+            })
+        })
+        .await;
+
+    let response = match buttons {
+        Some(buttons) => serenity::CreateInteractionResponse::UpdateMessage(
+            serenity::CreateInteractionResponseMessage::new().components(vec![buttons]),
+        ),
+        // The meme is older than the retention period and no longer tracked
+        None => serenity::CreateInteractionResponse::Acknowledge,
+    };
+    press.create_response(ctx, response).await?;
+    Ok(())
+}
+
+/// Parent command for memes.
+#[poise::command(
+    slash_command,
+    subcommands("random", "top"),
+    subcommand_required,
+    description_localized("de", "Memes anzeigen und bewerten.")
+)]
+pub async fn meme(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Command to fetch and display a meme with vote buttons.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `subreddit` - Optional subreddit to fetch the meme from.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if fetching or sending fails.
+#[poise::command(
+    slash_command,
+    name_localized("de", "zufall"),
+    description_localized("de", "Zeigt ein zufälliges Meme an.")
+)]
+pub async fn random(
+    ctx: Context<'_>,
+    #[description = "Subreddit to fetch the meme from"]
+    #[description_localized("de", "Subreddit, aus dem das Meme stammt")]
+    subreddit: Option<String>,
+) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+    let configs = ctx.data().configs();
+    let filters = ctx
+        .guild_id()
+        .and_then(|guild_id| configs.guild(guild_id.get()))
+        .and_then(|guild| guild.memes.as_ref())
+        .unwrap_or(&configs.memes);
+
+    let subreddit = subreddit.map(|name| name.trim().trim_start_matches("r/").to_string());
+    if let Some(name) = &subreddit {
+        if !meme_api::is_valid_subreddit(name) {
+            ctx.send(
+                CreateReply::default()
+                    .content(format!(
+                        "{}: `{}`",
+                        tr(lang, Message::InvalidSubreddit),
+                        name
+                    ))
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        }
+        if !filters.allows_subreddit(name) {
+            ctx.send(
+                CreateReply::default()
+                    .content(format!(
+                        "{}: r/{}",
+                        tr(lang, Message::SubredditNotAllowed),
+                        name
+                    ))
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        }
+    }
+
+    let nsfw_channel = is_nsfw_channel(ctx).await;
+    let history_key = match ctx.guild_id() {
+        Some(guild_id) => guild_id.to_string(),
+        None => ctx.channel_id().to_string(),
+    };
+    let recent: HashSet<String> = ctx.data().meme_history.with(|history| {
+        history
+            .get(&history_key)
+            .map(|urls| urls.iter().cloned().collect())
+            .unwrap_or_default()
+    });
+
+    for _ in 0..MAX_ATTEMPTS {
+        let mut batch = match take_batch(ctx.data(), subreddit.as_deref()).await {
+            Ok(batch) => batch,
+            Err(err) => {
+                eprintln!("Error fetching meme: {:?}", err);
+                ctx.say(tr(lang, Message::MemeFetchFailed)).await?;
+                return Err(err.into());
+            }
+        };
+
+        let mut picked = None;
+        while let Some(meme) = batch.pop() {
+            if recent.contains(&meme.url) {
+                continue;
+            }
+            if let Verdict::Post(hidden) = judge(&meme, filters, nsfw_channel) {
+                picked = Some((meme, hidden));
+                break;
+            }
+        }
+
+        if subreddit.is_none() {
+            return_to_pool(ctx.data(), batch).await;
+        }
+        if let Some((meme, hidden)) = picked {
+            return post_meme(ctx, &meme, hidden, history_key).await;
+        }
+    }
+
+    ctx.say(tr(lang, Message::NoSuitableMeme)).await?;
+    Ok(())
+}
+
+/// Command to show the best-rated memes of the past week in this guild.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+#[poise::command(
+    slash_command,
+    guild_only,
+    description_localized("de", "Zeigt die bestbewerteten Memes der Woche an.")
+)]
+pub async fn top(ctx: Context<'_>) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+    let guild = ctx.guild_id().map(|guild_id| guild_id.get());
+    let now = Utc::now().timestamp();
+
+    let mut ranking: Vec<(i32, String)> = ctx.data().votes.with(|memes| {
+        memes
+            .values()
+            .filter(|meme| meme.guild == guild && !meme.is_expired(now) && !meme.votes.is_empty())
+            .map(|meme| {
+                (
+                    meme.score(),
+                    format!(
+                        "[{}]({}) · r/{}",
+                        meme.title, meme.post_link, meme.subreddit
+                    ),
+                )
+            })
+            .collect()
+    });
+
+    if ranking.is_empty() {
+        ctx.say(tr(lang, Message::NoRatedMemes)).await?;
+        return Ok(());
+    }
+
+    ranking.sort_by_key(|(score, _)| Reverse(*score));
+    let lines: Vec<String> = ranking
+        .iter()
+        .take(TOP_SIZE)
+        .enumerate()
+        .map(|(rank, (score, meme))| format!("{}. **{:+}** {}", rank + 1, score, meme))
+        .collect();
+
+    let embed = CreateEmbed::new()
+        .title(tr(lang, Message::TopMemes))
+        .description(lines.join("\n"));
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a meme from a subreddit with the given flags.
+    fn meme(subreddit: &str, nsfw: bool, spoiler: bool) -> Meme {
+        Meme {
+            subreddit: subreddit.to_string(),
+            post_link: String::from("https://redd.it/1"),
+            title: String::from("Meme"),
+            url: String::from("https://i.redd.it/1.png"),
+            nsfw,
+            spoiler,
+            author: String::from("author"),
+            ups: 1,
+            preview: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn nsfw_memes_only_pass_in_nsfw_channels_by_default() {
+        let filters = MemesConfig::default();
+        let nsfw = meme("memes", true, false);
+
+        assert_eq!(judge(&nsfw, &filters, true), Verdict::Post(false));
+        assert_eq!(judge(&nsfw, &filters, false), Verdict::Refetch);
+        assert_eq!(
+            judge(&meme("memes", false, false), &filters, false),
+            Verdict::Post(false)
+        );
+    }
+
+    #[test]
+    fn policies_decide_how_flagged_memes_are_posted() {
+        assert_eq!(
+            apply_policy(ContentPolicy::Allow, false),
+            Verdict::Post(false)
+        );
+        assert_eq!(
+            apply_policy(ContentPolicy::NsfwChannels, true),
+            Verdict::Post(false)
+        );
+        assert_eq!(
+            apply_policy(ContentPolicy::NsfwChannels, false),
+            Verdict::Refetch
+        );
+        assert_eq!(
+            apply_policy(ContentPolicy::Spoiler, false),
+            Verdict::Post(true)
+        );
+        assert_eq!(apply_policy(ContentPolicy::Refetch, true), Verdict::Refetch);
+    }
+
+    #[test]
+    fn spoilers_are_hidden_unless_another_flag_refetches() {
+        let filters = MemesConfig {
+            nsfw: ContentPolicy::Refetch,
+            spoiler: ContentPolicy::Spoiler,
+            ..MemesConfig::default()
+        };
+
+        assert_eq!(
+            judge(&meme("memes", false, true), &filters, false),
+            Verdict::Post(true)
+        );
+        assert_eq!(
+            judge(&meme("memes", true, true), &filters, true),
+            Verdict::Refetch
+        );
+    }
+
+    #[test]
+    fn subreddit_lists_ignore_case() {
+        let filters = MemesConfig {
+            allow_subreddits: vec![String::from("ProgrammerHumor"), String::from("memes")],
+            deny_subreddits: vec![String::from("Memes")],
+            ..MemesConfig::default()
+        };
+
+        assert!(filters.allows_subreddit("programmerhumor"));
+        assert!(!filters.allows_subreddit("MEMES"));
+        assert!(!filters.allows_subreddit("dankmemes"));
+        assert_eq!(
+            judge(&meme("mEmEs", false, false), &filters, false),
+            Verdict::Refetch
+        );
+        assert!(MemesConfig::default().allows_subreddit("anything"));
+    }
+}
@@ -1,3 +1,19 @@
+pub mod alerts;
+pub mod cheapest;
 pub mod commands;
+pub mod compare;
+pub mod find;
+pub mod help;
+pub mod lunch;
+pub mod meme;
+pub mod trivia;
 
-pub use commands::{bot, daily_fact, meal, meme, random_fact, Data, Error};
+pub use alerts::alerts;
+pub use cheapest::cheapest;
+pub use commands::{bot, canteen, daily_fact, meal, random_fact, rate, sync, Data, Error};
+pub use compare::compare;
+pub use find::find;
+pub use help::help;
+pub use lunch::lunch;
+pub use meme::meme;
+pub use trivia::trivia;
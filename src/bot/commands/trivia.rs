@@ -0,0 +1,569 @@
+use poise::serenity_prelude::{self as serenity, Mentionable};
+use poise::CreateReply;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use super::commands::{fact_language, fact_languages, reply_language, Context, Error};
+use crate::apis::uselessfact;
+use crate::i18n::{tr, Language, Message};
+
+/// Time players have to answer a question.
+const TIME_LIMIT: Duration = Duration::from_secs(20);
+
+/// Number of answer options in a multiple choice question.
+const OPTION_COUNT: usize = 4;
+
+/// Number of players shown on the leaderboard.
+const LEADERBOARD_SIZE: usize = 10;
+
+/// Fabricated English facts used as distractors.
+const FABRICATED_EN: &[&str] = &[
+    "Octopuses have four hearts and two separate brains.",
+    "The Eiffel Tower was originally painted bright green.",
+    "Bats are blind and find their way only by smell.",
+    "A group of flamingos is called a 'parliament'.",
+    "The shortest war in history lasted exactly two days.",
+    "Bananas grow pointing straight down towards the ground.",
+    "Mount Everest is the tallest mountain on Earth measured from base to peak.",
+    "The first email was sent in 1959 between two universities.",
+    "Koalas are bears and hibernate through the winter.",
+    "Lightning never strikes the same place twice.",
+    "The average cloud weighs about as much as a bicycle.",
+    "Humans only use ten percent of their brains.",
+    "There are more trees on Earth than grains of sand on all beaches.",
+    "Snails have exactly 2,000 teeth arranged in a single row.",
+    "The Great Wall of China is visible from the Moon with the naked eye.",
+    "Giraffes sleep for about eight hours a night.",
+];
+
+/// Fabricated German facts used as distractors.
+const FABRICATED_DE: &[&str] = &[
+    "Oktopusse haben vier Herzen und zwei getrennte Gehirne.",
+    "Der Eiffelturm war ursprünglich leuchtend grün gestrichen.",
+    "Fledermäuse sind blind und orientieren sich nur über den Geruch.",
+    "Eine Gruppe von Flamingos nennt man 'Parlament'.",
+    "Der kürzeste Krieg der Geschichte dauerte genau zwei Tage.",
+    "Bananen wachsen senkrecht nach unten in Richtung Boden.",
+    "Der Mount Everest ist vom Fuß bis zum Gipfel gemessen der höchste Berg der Erde.",
+    "Die erste E-Mail wurde 1959 zwischen zwei Universitäten verschickt.",
+    "Koalas sind Bären und halten im Winter Winterschlaf.",
+    "Ein Blitz schlägt nie zweimal an derselben Stelle ein.",
+    "Eine durchschnittliche Wolke wiegt etwa so viel wie ein Fahrrad.",
+    "Menschen nutzen nur zehn Prozent ihres Gehirns.",
+    "Es gibt mehr Bäume auf der Erde als Sandkörner an allen Stränden.",
+    "Schnecken haben genau 2.000 Zähne in einer einzigen Reihe.",
+    "Die Chinesische Mauer ist vom Mond aus mit bloßem Auge sichtbar.",
+    "Giraffen schlafen etwa acht Stunden pro Nacht.",
+];
+
+/// The variants of the trivia game.
+#[derive(Debug, Clone, Copy, PartialEq, poise::ChoiceParameter)]
+pub enum TriviaMode {
+    /// Pick the true fact among fabricated ones.
+    #[name = "Multiple choice"]
+    #[name_localized("de", "Multiple Choice")]
+    MultipleChoice,
+    /// Decide whether a single fact is true.
+    #[name = "True or false"]
+    #[name_localized("de", "Wahr oder falsch")]
+    TrueFalse,
+}
+
+/// A question with its answer options.
+struct Question {
+    /// The text shown above the options.
+    prompt: &'static str,
+    /// The statement to judge in the true/false variant.
+    statement: Option<String>,
+    /// The answer options.
+    options: Vec<String>,
+    /// The index of the correct option.
+    answer: usize,
+}
+
+/// Returns the local pool of fabricated facts for a language.
+///
+/// # Arguments
+///
+/// * `code` - The language code of the facts.
+///
+/// # Returns
+///
+/// The fabricated facts, English ones for unknown languages.
+fn fabricated_pool(code: &str) -> &'static [&'static str] {
+    match Language::from_code(code) {
+        Some(Language::De) => FABRICATED_DE,
+        _ => FABRICATED_EN,
+    }
+}
+
+/// Creates a false variant of a fact by changing one of the numbers in it.
+///
+/// # Arguments
+///
+/// * `fact` - The true fact.
+/// * `rng` - The random number generator to use.
+///
+/// # Returns
+///
+/// The altered fact, or `None` if the fact contains no number.
+fn shuffle_numbers(fact: &str, rng: &mut impl Rng) -> Option<String> {
+    let mut numbers = Vec::new();
+    let mut start = None;
+    for (index, c) in fact.char_indices().chain([(fact.len(), ' ')]) {
+        match (c.is_ascii_digit(), start) {
+            (true, None) => start = Some(index),
+            (false, Some(begin)) => {
+                numbers.push((begin, index));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    let &(begin, end) = numbers.choose(rng)?;
+    let number: u64 = fact[begin..end].parse().ok()?;
+    let replacement = match rng.gen_range(0..3) {
+        0 => number.saturating_mul(2).max(2),
+        1 => number / 2,
+        _ => number + rng.gen_range(1..=9),
+    };
+    if replacement == number {
+        return None;
+    }
+
+    Some(format!("{}{}{}", &fact[..begin], replacement, &fact[end..]))
+}
+
+/// Generates false statements to mix with a true fact.
+///
+/// Distractors are altered versions of the fact itself and entries of the local pool,
+/// so no network access is required.
+///
+/// # Arguments
+///
+/// * `fact` - The true fact.
+/// * `code` - The language code of the fact.
+/// * `count` - The number of distractors to generate.
+/// * `rng` - The random number generator to use.
+///
+/// # Returns
+///
+/// Distinct false statements.
+fn distractors(fact: &str, code: &str, count: usize, rng: &mut impl Rng) -> Vec<String> {
+    let mut distractors = Vec::new();
+    if let Some(altered) = shuffle_numbers(fact, rng) {
+        distractors.push(altered);
+    }
+
+    let mut pool: Vec<&str> = fabricated_pool(code).to_vec();
+    pool.shuffle(rng);
+    for candidate in pool {
+        if candidate != fact && !distractors.iter().any(|distractor| distractor == candidate) {
+            distractors.push(String::from(candidate));
+        }
+    }
+
+    distractors.truncate(count);
+    distractors
+}
+
+/// Builds a question from a true fact.
+///
+/// # Arguments
+///
+/// * `fact` - The true fact.
+/// * `code` - The language code of the fact.
+/// * `mode` - The variant of the game.
+/// * `lang` - The language of the prompt and the true/false options.
+///
+/// # Returns
+///
+/// The question with shuffled options.
+fn build_question(fact: &str, code: &str, mode: TriviaMode, lang: Language) -> Question {
+    let mut rng = rand::thread_rng();
+
+    match mode {
+        TriviaMode::MultipleChoice => {
+            let mut options = distractors(fact, code, OPTION_COUNT - 1, &mut rng);
+            let answer = rng.gen_range(0..=options.len());
+            options.insert(answer, fact.to_string());
+            Question {
+                prompt: tr(lang, Message::TriviaWhichIsTrue),
+                statement: None,
+                options,
+                answer,
+            }
+        }
+        TriviaMode::TrueFalse => {
+            let statement = if rng.gen_bool(0.5) {
+                fact.to_string()
+            } else {
+                distractors(fact, code, 1, &mut rng)
+                    .pop()
+                    .unwrap_or_else(|| fact.to_string())
+            };
+            Question {
+                prompt: tr(lang, Message::TriviaTrueOrFalse),
+                answer: if statement == fact { 0 } else { 1 },
+                statement: Some(statement),
+                options: vec![
+                    tr(lang, Message::True).to_string(),
+                    tr(lang, Message::False).to_string(),
+                ],
+            }
+        }
+    }
+}
+
+/// Labels the answer options with letters.
+///
+/// # Arguments
+///
+/// * `index` - The index of the option.
+///
+/// # Returns
+///
+/// The label, e.g. `A` for the first option.
+fn option_label(index: usize) -> String {
+    char::from(b'A' + index as u8).to_string()
+}
+
+/// Builds the embed showing a question.
+///
+/// # Arguments
+///
+/// * `question` - The question to show.
+/// * `lang` - The language of the surrounding text.
+///
+/// # Returns
+///
+/// An embed listing the question and its options.
+fn question_embed(question: &Question, lang: Language) -> CreateEmbed {
+    let description = match &question.statement {
+        Some(statement) => statement.clone(),
+        None => question
+            .options
+            .iter()
+            .enumerate()
+            .map(|(index, option)| format!("**{}** {}", option_label(index), option))
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    };
+
+    CreateEmbed::new()
+        .title(question.prompt)
+        .description(description)
+        .footer(CreateEmbedFooter::new(format!(
+            "{}: {}s",
+            tr(lang, Message::TriviaTimeLimit),
+            TIME_LIMIT.as_secs()
+        )))
+}
+
+/// Creates the answer buttons of a question.
+///
+/// # Arguments
+///
+/// * `ctx_id` - The ID of the invocation, used as prefix for the button IDs.
+/// * `question` - The question to answer.
+///
+/// # Returns
+///
+/// A row of buttons, one per option.
+fn answer_buttons(ctx_id: u64, question: &Question) -> serenity::CreateActionRow {
+    let buttons = question
+        .options
+        .iter()
+        .enumerate()
+        .map(|(index, option)| {
+            // Multiple choice options are too long for a button, so they are shown as letters
+            let label = match question.statement {
+                Some(_) => option.clone(),
+                None => option_label(index),
+            };
+            serenity::CreateButton::new(format!("{}answer{}", ctx_id, index))
+                .label(label)
+                .style(serenity::ButtonStyle::Primary)
+        })
+        .collect();
+
+    serenity::CreateActionRow::Buttons(buttons)
+}
+
+/// Guess which useless fact is true and collect points for the server leaderboard.
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("play", "leaderboard"),
+    subcommand_required,
+    description_localized("de", "Errate, welcher nutzlose Fakt wahr ist, und sammle Punkte.")
+)]
+pub async fn trivia(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Command to play a round of trivia based on a random useless fact.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+/// * `mode` - Optional variant of the game (defaults to multiple choice).
+/// * `language` - Optional language code of the fact.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if fetching or sending fails.
+#[poise::command(
+    slash_command,
+    guild_only,
+    name_localized("de", "spielen"),
+    description_localized("de", "Spielt eine Runde Trivia mit einem nutzlosen Fakt.")
+)]
+pub async fn play(
+    ctx: Context<'_>,
+    #[description = "Game variant"]
+    #[description_localized("de", "Spielvariante")]
+    #[name_localized("de", "modus")]
+    mode: Option<TriviaMode>,
+    #[description = "Language of the fact"]
+    #[description_localized("de", "Sprache des Fakts")]
+    #[name_localized("de", "sprache")]
+    #[autocomplete = "fact_languages"]
+    language: Option<String>,
+) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+    let mode = mode.unwrap_or(TriviaMode::MultipleChoice);
+    let code = match fact_language(ctx, language) {
+        Ok(code) => code,
+        Err(message) => {
+            ctx.send(CreateReply::default().content(message).ephemeral(true))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let fact = match uselessfact::random(Some(code.clone())).await {
+        Ok(fact) => fact,
+        Err(err) => {
+            eprintln!("Error fetching random fact: {:?}", err);
+            ctx.say(tr(lang, Message::RandomFactFetchFailed)).await?;
+            return Err(err.into());
+        }
+    };
+
+    let question = build_question(&fact.text, &code, mode, lang);
+    let ctx_id = ctx.id();
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .embed(question_embed(&question, lang))
+                .components(vec![answer_buttons(ctx_id, &question)]),
+        )
+        .await?;
+
+    // Only the first answer of each player counts
+    let mut answered = HashSet::new();
+    let mut winners = Vec::new();
+    let deadline = Instant::now() + TIME_LIMIT;
+    while let Some(press) = serenity::ComponentInteractionCollector::new(ctx)
+        .filter(move |press| press.data.custom_id.starts_with(&ctx_id.to_string()))
+        .timeout(deadline.saturating_duration_since(Instant::now()))
+        .await
+    {
+        let message = if answered.insert(press.user.id) {
+            let choice = press
+                .data
+                .custom_id
+                .rsplit("answer")
+                .next()
+                .and_then(|index| index.parse::<usize>().ok());
+            if choice == Some(question.answer) {
+                winners.push(press.user.id);
+            }
+            tr(lang, Message::TriviaAnswerRecorded)
+        } else {
+            tr(lang, Message::TriviaAlreadyAnswered)
+        };
+
+        press
+            .create_response(
+                ctx,
+                serenity::CreateInteractionResponse::Message(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .content(message)
+                        .ephemeral(true),
+                ),
+            )
+            .await?;
+    }
+
+    if let Some(guild_id) = ctx.guild_id() {
+        ctx.data()
+            .trivia_scores
+            .update(|scores| {
+                let guild_scores = scores.entry(guild_id.to_string()).or_default();
+                for winner in &winners {
+                    *guild_scores.entry(winner.to_string()).or_default() += 1;
+                }
+            })
+            .await;
+    }
+
+    let correct = match question.statement {
+        Some(_) => question.options[question.answer].clone(),
+        None => format!(
+            "**{}** {}",
+            option_label(question.answer),
+            question.options[question.answer]
+        ),
+    };
+    let result = if winners.is_empty() {
+        tr(lang, Message::TriviaNoWinners).to_string()
+    } else {
+        let mentions: Vec<String> = winners.iter().map(|id| id.mention().to_string()).collect();
+        format!(
+            "{}: {}",
+            tr(lang, Message::TriviaWinners),
+            mentions.join(", ")
+        )
+    };
+
+    let embed = question_embed(&question, lang)
+        .field(tr(lang, Message::TriviaCorrectAnswer), correct, false)
+        .field(tr(lang, Message::Source), fact.permalink, false)
+        .footer(CreateEmbedFooter::new(result));
+    reply
+        .edit(ctx, CreateReply::default().embed(embed).components(vec![]))
+        .await?;
+    Ok(())
+}
+
+/// Command to show the players with the most correct trivia answers in this guild.
+///
+/// # Arguments
+///
+/// * `ctx` - The context for executing the command.
+///
+/// # Returns
+///
+/// A result indicating success (`Ok`) or an error (`Err`) if sending fails.
+#[poise::command(
+    slash_command,
+    guild_only,
+    name_localized("de", "rangliste"),
+    description_localized("de", "Zeigt die besten Trivia-Spieler dieses Servers.")
+)]
+pub async fn leaderboard(ctx: Context<'_>) -> Result<(), Error> {
+    let lang = reply_language(ctx);
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    let mut ranking: Vec<(String, u32)> = ctx.data().trivia_scores.with(|scores| {
+        scores
+            .get(&guild_id.to_string())
+            .map(|guild_scores| {
+                guild_scores
+                    .iter()
+                    .map(|(user, points)| (user.clone(), *points))
+                    .collect()
+            })
+            .unwrap_or_default()
+    });
+
+    if ranking.is_empty() {
+        ctx.say(tr(lang, Message::TriviaNoScores)).await?;
+        return Ok(());
+    }
+
+    ranking.sort_by_key(|(_, points)| Reverse(*points));
+    let lines: Vec<String> = ranking
+        .iter()
+        .take(LEADERBOARD_SIZE)
+        .enumerate()
+        .map(|(rank, (user, points))| {
+            format!(
+                "{}. <@{}> — {} {}",
+                rank + 1,
+                user,
+                points,
+                tr(lang, Message::TriviaPoints)
+            )
+        })
+        .collect();
+
+    let embed = CreateEmbed::new()
+        .title(tr(lang, Message::TriviaLeaderboard))
+        .description(lines.join("\n"));
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const FACT: &str =
+        "A blue whale's heart weighs about 180 kilograms and beats 8 times a minute.";
+
+    #[test]
+    fn shuffle_numbers_changes_a_number() {
+        for seed in 0..200 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            if let Some(altered) = shuffle_numbers(FACT, &mut rng) {
+                assert_ne!(altered, FACT);
+                assert!(altered.starts_with("A blue whale's heart weighs about "));
+            }
+        }
+        assert_eq!(
+            shuffle_numbers("No numbers here.", &mut StdRng::seed_from_u64(0)),
+            None
+        );
+    }
+
+    #[test]
+    fn distractors_are_unique_and_never_the_answer() {
+        for code in ["en", "de"] {
+            // The fact itself is part of the pool, so it has to be filtered out
+            let fact = fabricated_pool(code)[0];
+            for seed in 0..200 {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let distractors = distractors(fact, code, OPTION_COUNT - 1, &mut rng);
+
+                assert_eq!(distractors.len(), OPTION_COUNT - 1);
+                assert!(distractors.iter().all(|distractor| distractor != fact));
+                let unique: HashSet<&String> = distractors.iter().collect();
+                assert_eq!(unique.len(), distractors.len());
+            }
+        }
+    }
+
+    #[test]
+    fn questions_contain_the_answer() {
+        for _ in 0..200 {
+            let question = build_question(FACT, "en", TriviaMode::MultipleChoice, Language::En);
+
+            assert_eq!(question.options.len(), OPTION_COUNT);
+            assert_eq!(question.options[question.answer], FACT);
+            assert_eq!(
+                question
+                    .options
+                    .iter()
+                    .filter(|option| *option == FACT)
+                    .count(),
+                1
+            );
+
+            let question = build_question(FACT, "en", TriviaMode::TrueFalse, Language::En);
+            let statement = question.statement.unwrap();
+            assert_eq!(question.answer == 0, statement == FACT);
+        }
+    }
+}
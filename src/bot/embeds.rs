@@ -0,0 +1,68 @@
+use crate::apis::meme_api::Meme;
+use crate::apis::uselessfact::UselessFact;
+use crate::i18n::{tr, Language, Message};
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+
+/// Maximum length of an embed title.
+const TITLE_LIMIT: usize = 256;
+
+/// Builds an embed for a useless fact, including its source and permalink.
+///
+/// # Arguments
+///
+/// * `fact` - The fact to display.
+/// * `title` - The title of the embed.
+/// * `language` - The language used for the field names.
+///
+/// # Returns
+///
+/// An embed showing the fact text with attribution links.
+pub fn fact_embed(fact: &UselessFact, title: &str, language: Language) -> CreateEmbed {
+    CreateEmbed::new()
+        .title(title)
+        .url(&fact.permalink)
+        .description(&fact.text)
+        .field(
+            tr(language, Message::Source),
+            format!("[{}]({})", fact.source, fact.source_url),
+            false,
+        )
+        .footer(CreateEmbedFooter::new(format!(
+            "uselessfacts.jsph.pl · {}",
+            fact.language
+        )))
+}
+
+/// Builds an embed for a meme, including its author, subreddit and upvotes.
+///
+/// # Arguments
+///
+/// * `meme` - The meme to display.
+/// * `hidden` - Whether the image is left out because it must be hidden behind a spoiler.
+/// * `language` - The language used for the field names.
+///
+/// # Returns
+///
+/// An embed showing the meme with attribution.
+pub fn meme_embed(meme: &Meme, hidden: bool, language: Language) -> CreateEmbed {
+    let embed = CreateEmbed::new()
+        .title(meme.title.chars().take(TITLE_LIMIT).collect::<String>())
+        .url(&meme.post_link)
+        .field(
+            tr(language, Message::Author),
+            format!("u/{}", meme.author),
+            true,
+        )
+        .field(
+            tr(language, Message::Subreddit),
+            format!("r/{}", meme.subreddit),
+            true,
+        )
+        .field(tr(language, Message::Upvotes), meme.ups.to_string(), true);
+
+    if hidden {
+        embed
+    } else {
+        embed.image(&meme.url)
+    }
+}
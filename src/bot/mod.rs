@@ -1,4 +1,9 @@
 pub mod bot;
 pub mod commands;
+pub mod embeds;
+pub mod registration;
+pub mod reload;
+pub mod scheduler;
 
 pub use bot::start_bot;
+pub use registration::{unregister_commands, Registration};
@@ -0,0 +1,90 @@
+use super::commands::{Data, Error};
+use poise::serenity_prelude as serenity;
+
+/// Describes where the application commands of the bot are registered.
+#[derive(Clone, Debug)]
+pub enum Registration {
+    /// Register commands globally, which can take up to an hour to propagate.
+    Global,
+    /// Register commands only in the given development guilds, which is instant.
+    Guilds(Vec<serenity::GuildId>),
+}
+
+impl Registration {
+    /// Creates the registration mode for a list of guild IDs.
+    ///
+    /// # Arguments
+    ///
+    /// * `guilds` - IDs of the development guilds, an empty list means global registration.
+    ///
+    /// # Returns
+    ///
+    /// `Registration::Global` if no guild is given, otherwise `Registration::Guilds`.
+    pub fn from_guild_ids(guilds: &[u64]) -> Self {
+        if guilds.is_empty() {
+            Registration::Global
+        } else {
+            Registration::Guilds(guilds.iter().copied().map(serenity::GuildId::new).collect())
+        }
+    }
+}
+
+/// Registers the given commands according to the registration mode.
+///
+/// # Arguments
+///
+/// * `http` - The HTTP client used to talk to Discord.
+/// * `commands` - The commands to register.
+/// * `registration` - Where to register the commands.
+///
+/// # Returns
+///
+/// `Ok(())` if all commands were registered, otherwise the Discord error.
+pub async fn register_commands(
+    http: impl AsRef<serenity::Http>,
+    commands: &[poise::Command<Data, Error>],
+    registration: &Registration,
+) -> Result<(), serenity::Error> {
+    match registration {
+        Registration::Global => poise::builtins::register_globally(http, commands).await,
+        Registration::Guilds(guilds) => {
+            for guild in guilds {
+                poise::builtins::register_in_guild(http.as_ref(), commands, *guild).await?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Removes all global commands and the commands of the given guilds.
+///
+/// Only the HTTP API is used, so no gateway connection is opened.
+///
+/// # Arguments
+///
+/// * `token` - The Discord bot token.
+/// * `registration` - The guilds whose commands should be removed as well.
+///
+/// # Returns
+///
+/// `Ok(())` if all commands were removed, otherwise the Discord error.
+pub async fn unregister_commands(
+    token: &str,
+    registration: &Registration,
+) -> Result<(), serenity::Error> {
+    let http = serenity::Http::new(token);
+    let application = http.get_current_application_info().await?;
+    http.set_application_id(application.id);
+
+    serenity::Command::set_global_commands(&http, vec![]).await?;
+    println!("Removed global commands");
+
+    if let Registration::Guilds(guilds) = registration {
+        for guild in guilds {
+            guild.set_commands(&http, vec![]).await?;
+            println!("Removed commands of guild {}", guild);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use super::commands::Data;
+use super::scheduler::Jobs;
+use crate::config::{self, Configs};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// How often the config file is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Listens for SIGHUP, which requests a reload of the configuration.
+struct Hangup {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl Hangup {
+    /// Starts listening for SIGHUP.
+    fn listen() -> Self {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+
+            let signal = signal(SignalKind::hangup())
+                .map_err(|err| eprintln!("Failed to listen for SIGHUP: {}", err))
+                .ok();
+            Hangup { signal }
+        }
+
+        #[cfg(not(unix))]
+        Hangup {}
+    }
+
+    /// Waits for the next SIGHUP, forever if signals are not available.
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(signal) = self.signal.as_mut() {
+            if signal.recv().await.is_some() {
+                return;
+            }
+        }
+
+        std::future::pending::<()>().await
+    }
+}
+
+/// Returns when the config file was last modified.
+///
+/// # Arguments
+///
+/// * `path` - The path of the configuration file.
+///
+/// # Returns
+///
+/// The modification time, or `None` if the file does not exist.
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Adds a description of a changed setting to the list of changes.
+///
+/// # Arguments
+///
+/// * `changes` - The list of changes.
+/// * `name` - The dotted path of the setting.
+/// * `old` - The previous value.
+/// * `new` - The reloaded value.
+fn diff<T: PartialEq + Debug>(changes: &mut Vec<String>, name: &str, old: &T, new: &T) {
+    if old != new {
+        changes.push(format!("{}: {:?} -> {:?}", name, old, new));
+    }
+}
+
+/// Describes the differences between two configurations for the log.
+///
+/// The list is only used for logging, whether the configuration changed is decided
+/// by comparing the whole configurations.
+///
+/// # Arguments
+///
+/// * `old` - The previous configuration.
+/// * `new` - The reloaded configuration.
+///
+/// # Returns
+///
+/// One line per changed setting, empty if nothing changed.
+fn changes(old: &Configs, new: &Configs) -> Vec<String> {
+    let mut changes = Vec::new();
+    diff(&mut changes, "language", &old.language, &new.language);
+    diff(&mut changes, "timezone", &old.timezone, &new.timezone);
+    diff(
+        &mut changes,
+        "locations.cities",
+        &old.locations.cities,
+        &new.locations.cities,
+    );
+    diff(
+        &mut changes,
+        "locations.canteens",
+        &old.locations.canteens,
+        &new.locations.canteens,
+    );
+    diff(
+        &mut changes,
+        "locations.coordinates",
+        &old.locations.coordinates,
+        &new.locations.coordinates,
+    );
+    diff(&mut changes, "bot.guilds", &old.bot.guilds, &new.bot.guilds);
+    diff(&mut changes, "bot.token", &old.bot.token, &new.bot.token);
+    diff(
+        &mut changes,
+        "facts.language",
+        &old.facts.language,
+        &new.facts.language,
+    );
+    diff(
+        &mut changes,
+        "facts.announcements",
+        &old.facts.announcements,
+        &new.facts.announcements,
+    );
+    diff(&mut changes, "memes", &old.memes, &new.memes);
+
+    let mut guilds: Vec<&String> = old.guilds.keys().chain(new.guilds.keys()).collect();
+    guilds.sort();
+    guilds.dedup();
+    for guild in guilds {
+        diff(
+            &mut changes,
+            &format!("guilds.{}", guild),
+            &old.guilds.get(guild),
+            &new.guilds.get(guild),
+        );
+    }
+    changes
+}
+
+/// Reloads the configuration and swaps it in if it is valid.
+///
+/// On any error the current configuration is kept.
+///
+/// # Arguments
+///
+/// * `path` - The path of the configuration file.
+/// * `data` - The shared bot state holding the configuration.
+/// * `jobs` - The background jobs, restarted if the announcements or a timezone changed.
+fn reload(path: &Path, data: &Data, jobs: &Mutex<Jobs>) {
+    let configs =
+        match config::load(path, true).and_then(|configs| configs.validate().map(|()| configs)) {
+            Ok(configs) => configs,
+            Err(err) => {
+                eprintln!("Keeping the current configuration: {}", err);
+                return;
+            }
+        };
+
+    if *data.configs() == configs {
+        println!("Configuration unchanged");
+        return;
+    }
+
+    let old = data.replace_configs(configs);
+    let new = data.configs();
+    let changes = changes(&old, &new);
+    if changes.is_empty() {
+        println!("Configuration changed");
+    }
+    for change in &changes {
+        println!("Configuration changed: {}", change);
+    }
+
+    let guild_timezones = |configs: &Configs| -> HashMap<String, Option<Tz>> {
+        configs
+            .guilds
+            .iter()
+            .map(|(guild, settings)| (guild.clone(), settings.timezone))
+            .collect()
+    };
+    if old.facts.announcements != new.facts.announcements
+        || old.timezone != new.timezone
+        || guild_timezones(&old) != guild_timezones(&new)
+    {
+        jobs.lock().unwrap().restart_announcements(data);
+    }
+    if old.bot.guilds != new.bot.guilds {
+        println!("Changes to `bot.guilds` take effect after a restart");
+    }
+}
+
+/// Reloads the configuration whenever the config file changes or SIGHUP is received.
+///
+/// # Arguments
+///
+/// * `path` - The path of the configuration file.
+/// * `data` - The shared bot state holding the configuration.
+/// * `jobs` - The background jobs, restarted if the announcements changed.
+pub async fn watch_config(path: PathBuf, data: Data, jobs: Arc<Mutex<Jobs>>) {
+    let mut hangup = Hangup::listen();
+    let mut modified = modified_at(&path);
+
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(POLL_INTERVAL) => {
+                let current = modified_at(&path);
+                if current == modified {
+                    continue;
+                }
+                modified = current;
+                println!("Config file changed, reloading...");
+            }
+            _ = hangup.recv() => println!("Received SIGHUP, reloading configuration..."),
+        }
+
+        reload(&path, &data, &jobs);
+    }
+}
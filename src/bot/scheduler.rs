@@ -0,0 +1,401 @@
+use super::commands::{alerts::Alert, Data};
+use super::embeds::fact_embed;
+use crate::apis::uselessfact;
+use crate::clock;
+use crate::config::Announcement;
+use crate::i18n::{tr, Language, Message};
+use chrono::{NaiveDate, NaiveTime, TimeDelta};
+use openmensa_rust_interface::{get_canteens_by_ids, get_meals, Canteen};
+use poise::serenity_prelude as serenity;
+use regex::Regex;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+/// Number of posted fact IDs remembered per channel.
+const HISTORY_SIZE: usize = 365;
+
+/// Default time of day for announcements if none is configured.
+const DEFAULT_TIME: &str = "08:00";
+
+/// Time of day at which upcoming menus are scanned for dish alerts.
+const ALERT_SCAN_TIME: &str = "07:00";
+
+/// Number of days, starting today, whose menus are scanned for dish alerts.
+const ALERT_SCAN_DAYS: i64 = 7;
+
+/// Background jobs of the bot: the dish alert scan and the fact-of-the-day announcements.
+///
+/// The jobs only use the HTTP API, so they keep running while the gateway reconnects.
+pub struct Jobs {
+    /// The HTTP client used to post messages.
+    http: Arc<serenity::Http>,
+    /// The job scanning menus for dish alerts.
+    alert_scan: JoinHandle<()>,
+    /// One job per configured announcement.
+    announcements: Vec<JoinHandle<()>>,
+}
+
+impl Jobs {
+    /// Starts the dish alert scan and one job per configured fact-of-the-day announcement.
+    ///
+    /// # Arguments
+    ///
+    /// * `http` - The HTTP client used to post messages.
+    /// * `data` - The shared bot state.
+    ///
+    /// # Returns
+    ///
+    /// The started jobs.
+    pub fn spawn(http: Arc<serenity::Http>, data: &Data) -> Self {
+        Jobs {
+            alert_scan: tokio::spawn(scan_alerts_daily(http.clone(), data.clone())),
+            announcements: spawn_announcements(&http, data),
+            http,
+        }
+    }
+
+    /// Restarts the announcement jobs, e.g. after the configuration was reloaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The shared bot state.
+    pub fn restart_announcements(&mut self, data: &Data) {
+        for job in self.announcements.drain(..) {
+            job.abort();
+        }
+        self.announcements = spawn_announcements(&self.http, data);
+    }
+
+    /// Stops all jobs.
+    pub fn abort(&mut self) {
+        self.alert_scan.abort();
+        for job in self.announcements.drain(..) {
+            job.abort();
+        }
+    }
+}
+
+/// Starts one background job per configured fact-of-the-day announcement.
+///
+/// # Arguments
+///
+/// * `http` - The HTTP client used to post messages.
+/// * `data` - The shared bot state.
+///
+/// # Returns
+///
+/// The handles of the started jobs.
+fn spawn_announcements(http: &Arc<serenity::Http>, data: &Data) -> Vec<JoinHandle<()>> {
+    data.configs()
+        .facts
+        .announcements
+        .iter()
+        .filter_map(|announcement| {
+            let time = announcement.time.as_deref().unwrap_or(DEFAULT_TIME);
+            let time = match NaiveTime::parse_from_str(time, "%H:%M") {
+                Ok(time) => time,
+                Err(err) => {
+                    eprintln!(
+                        "Invalid announcement time \"{}\" for channel {}: {}",
+                        time, announcement.channel, err
+                    );
+                    return None;
+                }
+            };
+
+            let job = announce_daily_fact(http.clone(), data.clone(), announcement.clone(), time);
+            Some(tokio::spawn(job))
+        })
+        .collect()
+}
+
+/// Posts the daily fact into a channel every day at the given time.
+///
+/// If the bot starts after today's announcement time, the fact is posted right away
+/// unless it was already posted.
+///
+/// # Arguments
+///
+/// * `http` - The HTTP client used to post messages.
+/// * `data` - The shared bot state.
+/// * `announcement` - The configured announcement.
+/// * `time` - The time of day to post at, in the timezone of the channel's guild.
+async fn announce_daily_fact(
+    http: Arc<serenity::Http>,
+    data: Data,
+    announcement: Announcement,
+    time: NaiveTime,
+) {
+    let guild = channel_guild(&http, announcement.channel).await;
+
+    loop {
+        let timezone = data.configs().timezone_for(guild);
+        let now = clock::now(timezone);
+        let today = now.date().and_time(time);
+
+        if now >= today {
+            post_daily_fact(&http, &data, &announcement).await;
+        }
+
+        let next = if now >= today {
+            today + TimeDelta::days(1)
+        } else {
+            today
+        };
+        tokio::time::sleep(clock::until(timezone, next)).await;
+    }
+}
+
+/// Looks up the guild a channel belongs to.
+///
+/// # Arguments
+///
+/// * `http` - The HTTP client used to fetch the channel.
+/// * `channel` - The ID of the channel.
+///
+/// # Returns
+///
+/// The ID of the guild, or `None` for channels outside of guilds or if the channel can not be fetched.
+async fn channel_guild(http: &serenity::Http, channel: u64) -> Option<u64> {
+    match serenity::ChannelId::new(channel).to_channel(http).await {
+        Ok(serenity::Channel::Guild(channel)) => Some(channel.guild_id.get()),
+        Ok(_) => None,
+        Err(err) => {
+            eprintln!(
+                "Failed to look up the guild of channel {}, using the global timezone: {}",
+                channel, err
+            );
+            None
+        }
+    }
+}
+
+/// Fetches the daily fact and posts it, unless the channel already got this fact.
+///
+/// # Arguments
+///
+/// * `http` - The HTTP client used to post messages.
+/// * `data` - The shared bot state.
+/// * `announcement` - The configured announcement.
+async fn post_daily_fact(http: &serenity::Http, data: &Data, announcement: &Announcement) {
+    let configs = data.configs();
+    let fact_language =
+        match configs.fact_language(announcement.language.as_deref(), configs.language) {
+            Ok(code) => code,
+            Err(err) => {
+                eprintln!("{} (channel {})", err, announcement.channel);
+                return;
+            }
+        };
+
+    let fact = match uselessfact::daily(Some(fact_language.clone())).await {
+        Ok(fact) => fact,
+        Err(err) => {
+            eprintln!("Error fetching daily fact: {:?}", err);
+            return;
+        }
+    };
+
+    let key = announcement.channel.to_string();
+    let already_posted = data
+        .fact_history
+        .with(|history| history.get(&key).is_some_and(|ids| ids.contains(&fact.id)));
+    if already_posted {
+        return;
+    }
+
+    let language = Language::from_code(&fact_language).unwrap_or(data.configs().language);
+    let embed = fact_embed(&fact, tr(language, Message::FactOfTheDay), language);
+    let message = serenity::CreateMessage::new().embed(embed);
+
+    match serenity::ChannelId::new(announcement.channel)
+        .send_message(http, message)
+        .await
+    {
+        Ok(_) => {
+            data.fact_history
+                .update(|history| {
+                    let ids = history.entry(key).or_default();
+                    ids.push(fact.id);
+                    if ids.len() > HISTORY_SIZE {
+                        ids.remove(0);
+                    }
+                })
+                .await
+        }
+        Err(err) => eprintln!(
+            "Failed to post daily fact to channel {}: {}",
+            announcement.channel, err
+        ),
+    }
+}
+
+/// Scans the upcoming menus for dish alerts right away and then every day.
+///
+/// # Arguments
+///
+/// * `http` - The HTTP client used to send direct messages.
+/// * `data` - The shared bot state.
+async fn scan_alerts_daily(http: Arc<serenity::Http>, data: Data) {
+    let time = NaiveTime::parse_from_str(ALERT_SCAN_TIME, "%H:%M").unwrap_or_default();
+
+    loop {
+        scan_alerts(&http, &data).await;
+
+        let timezone = data.configs().timezone_for(None);
+        let now = clock::now(timezone);
+        let mut next = now.date().and_time(time);
+        if next <= now {
+            next += TimeDelta::days(1);
+        }
+        tokio::time::sleep(clock::until(timezone, next)).await;
+    }
+}
+
+/// Notifies subscribers about meals matching their alerts in the upcoming menus.
+///
+/// Each user is notified at most once per canteen, date and meal.
+///
+/// # Arguments
+///
+/// * `http` - The HTTP client used to send direct messages.
+/// * `data` - The shared bot state.
+async fn scan_alerts(http: &serenity::Http, data: &Data) {
+    let configs = data.configs();
+    let configured = &configs.locations.canteens;
+    let alerts: Vec<(String, Alert, Regex)> = data.alerts.with(|state| {
+        state
+            .alerts
+            .iter()
+            .flat_map(|(user, alerts)| alerts.iter().map(move |alert| (user, alert)))
+            .filter_map(|(user, alert)| {
+                let matcher = alert.matcher().ok()?;
+                Some((user.clone(), alert.clone(), matcher))
+            })
+            .collect()
+    });
+    if alerts.is_empty() {
+        return;
+    }
+
+    let unwatched = alerts
+        .iter()
+        .filter(|(_, alert, _)| alert.canteen.is_none())
+        .count();
+    if configured.is_empty() && unwatched > 0 {
+        eprintln!(
+            "{} dish alerts without a canteen are skipped because locations.canteens is empty",
+            unwatched
+        );
+    }
+
+    // Notifications of past days can not be sent again, so they are forgotten
+    let today = clock::today(configs.timezone_for(None));
+    data.alerts
+        .update(|state| {
+            state.notified.retain(|key| {
+                key.split('/')
+                    .next()
+                    .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+                    .is_some_and(|date| date >= today)
+            })
+        })
+        .await;
+
+    let mut canteen_ids = HashSet::new();
+    for (_, alert, _) in &alerts {
+        match alert.canteen {
+            Some(id) => {
+                canteen_ids.insert(id);
+            }
+            None => canteen_ids.extend(configured),
+        }
+    }
+
+    let canteens = match get_canteens_by_ids(canteen_ids.into_iter().collect()).await {
+        Ok(canteens) => canteens,
+        Err(err) => {
+            eprintln!("Error fetching canteens for dish alerts: {:?}", err);
+            return;
+        }
+    };
+
+    for canteen in &canteens {
+        for offset in 0..ALERT_SCAN_DAYS {
+            let date = today + TimeDelta::days(offset);
+            // Closed days have no menu
+            let Ok(meals) = get_meals(canteen, &date.to_string()).await else {
+                continue;
+            };
+
+            for (user, alert, matcher) in &alerts {
+                let watched = match alert.canteen {
+                    Some(id) => id == canteen.id,
+                    None => configured.contains(&canteen.id),
+                };
+                if !watched {
+                    continue;
+                }
+
+                for meal in meals.iter().filter(|meal| matcher.is_match(&meal.name)) {
+                    let key = format!("{}/{}/{}/{}", date, user, canteen.id, meal.name);
+                    if data.alerts.with(|state| state.notified.contains(&key)) {
+                        continue;
+                    }
+
+                    if notify_alert(http, user, alert, canteen, &meal.name, date).await {
+                        data.alerts.update(|state| state.notified.insert(key)).await;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Sends a direct message about a meal matching an alert.
+///
+/// # Arguments
+///
+/// * `http` - The HTTP client used to send the message.
+/// * `user` - The ID of the subscribed user.
+/// * `alert` - The matching alert.
+/// * `canteen` - The canteen serving the meal.
+/// * `meal` - The name of the meal.
+/// * `date` - The date the meal is served on.
+///
+/// # Returns
+///
+/// `true` if the message was sent.
+async fn notify_alert(
+    http: &serenity::Http,
+    user: &str,
+    alert: &Alert,
+    canteen: &Canteen,
+    meal: &str,
+    date: NaiveDate,
+) -> bool {
+    let Ok(user_id) = user.parse::<u64>() else {
+        return false;
+    };
+
+    let content = format!(
+        "{} **{}** — {}, {} (`{}`)",
+        tr(alert.language, Message::AlertMatched),
+        meal,
+        canteen.name,
+        date,
+        alert.pattern
+    );
+
+    match serenity::UserId::new(user_id)
+        .direct_message(http, serenity::CreateMessage::new().content(content))
+        .await
+    {
+        Ok(_) => true,
+        Err(err) => {
+            eprintln!("Failed to send dish alert to user {}: {}", user, err);
+            false
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::i18n::{tr, Language, Message};
+use crate::models::canteen::{self, distance_km};
+use crate::models::{CanteenInfo, TabledCanteen};
+use chrono::NaiveDate;
+use openmensa_rust_interface::{get_all_canteens, get_canteens_by_location, Canteen};
+use tabled::{settings::Style, Table};
+
+/// Prints details and the opening days of a canteen.
+///
+/// # Arguments
+///
+/// * `canteen` - The ID or the exact name of the canteen.
+/// * `today` - The first day of the listed week.
+/// * `language` - The language used for the output.
+///
+/// # Returns
+///
+/// `Ok(())` if the canteen was printed, otherwise returns an error message as a `String`.
+pub async fn show(canteen: &str, today: NaiveDate, language: Language) -> Result<(), String> {
+    let canteen = match canteen::find(canteen).await {
+        Ok(Some(canteen)) => canteen,
+        Ok(None) => return Err(tr(language, Message::CanteenNotFound).to_string()),
+        Err(err) => {
+            return Err(format!(
+                "{} {}",
+                tr(language, Message::CanteenFetchFailed),
+                err
+            ))
+        }
+    };
+
+    let info = CanteenInfo::fetch(canteen, today).await;
+    for (name, value) in info.fields(language) {
+        match value.split_once('\n') {
+            // Multi-line values are printed as an indented block
+            Some(_) => {
+                println!("{}:", name);
+                for line in value.lines() {
+                    println!("  {}", line);
+                }
+            }
+            None => println!("{}: {}", name, value),
+        }
+    }
+    Ok(())
+}
+
+/// Prints canteens as a table or as JSON.
+///
+/// # Arguments
+///
+/// * `canteens` - The canteens to print.
+/// * `json` - Whether to print JSON instead of a table.
+/// * `language` - The language used for the table headers.
+///
+/// # Returns
+///
+/// `Ok(())` if the canteens were printed, otherwise returns an error message as a `String`.
+fn print_canteens(canteens: Vec<Canteen>, json: bool, language: Language) -> Result<(), String> {
+    let mut canteens: Vec<TabledCanteen> = canteens.into_iter().map(TabledCanteen::from).collect();
+    canteens.sort_by_key(|canteen| canteen.id);
+
+    if json {
+        let json = serde_json::to_string_pretty(&canteens).map_err(|err| err.to_string())?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    if canteens.is_empty() {
+        println!("{}", tr(language, Message::NoCanteens));
+        return Ok(());
+    }
+
+    let mut builder = Table::builder(&canteens);
+    builder.remove_record(0);
+    builder.insert_record(
+        0,
+        [
+            "ID",
+            tr(language, Message::Name),
+            tr(language, Message::City),
+            tr(language, Message::Address),
+        ],
+    );
+
+    let mut table = builder.build();
+    table.with(Style::modern());
+    println!("{}", table);
+    Ok(())
+}
+
+/// Prints all canteens.
+///
+/// # Arguments
+///
+/// * `json` - Whether to print JSON instead of a table.
+/// * `language` - The language used for the output.
+///
+/// # Returns
+///
+/// `Ok(())` if the canteens were printed, otherwise returns an error message as a `String`.
+pub async fn list(json: bool, language: Language) -> Result<(), String> {
+    let canteens = get_all_canteens()
+        .await
+        .map_err(|err| format!("{} {}", tr(language, Message::CanteenFetchFailed), err))?;
+
+    print_canteens(canteens, json, language)
+}
+
+/// Criteria for searching canteens.
+pub struct Search<'a> {
+    /// Part of the canteen name (case-insensitive).
+    pub name: Option<&'a str>,
+    /// City the canteens are located in.
+    pub city: Option<&'a str>,
+    /// Latitude and longitude of the point to search around.
+    pub near: Option<(f64, f64)>,
+    /// Search radius around the point in kilometres.
+    pub radius: f64,
+}
+
+/// Prints the canteens matching all given criteria.
+///
+/// # Arguments
+///
+/// * `search` - The search criteria.
+/// * `json` - Whether to print JSON instead of a table.
+/// * `language` - The language used for the output.
+///
+/// # Returns
+///
+/// `Ok(())` if the canteens were printed, otherwise returns an error message as a `String`.
+pub async fn search(search: Search<'_>, json: bool, language: Language) -> Result<(), String> {
+    let canteens = match search.city {
+        Some(city) => get_canteens_by_location(city).await,
+        None => get_all_canteens().await,
+    }
+    .map_err(|err| format!("{} {}", tr(language, Message::CanteenFetchFailed), err))?;
+
+    let name = search.name.map(str::to_lowercase);
+    let canteens = canteens
+        .into_iter()
+        .filter(|canteen| {
+            name.as_ref()
+                .is_none_or(|name| canteen.name.to_lowercase().contains(name))
+        })
+        .filter(|canteen| {
+            search.near.is_none_or(|(latitude, longitude)| {
+                distance_km(canteen, latitude, longitude)
+                    .is_some_and(|distance| distance <= search.radius)
+            })
+        })
+        .collect();
+
+    print_canteens(canteens, json, language)
+}
@@ -0,0 +1,71 @@
+use crate::i18n::{self, tr, Language, Message};
+use crate::models::menu;
+use crate::models::price::format_price;
+use crate::models::PriceGroup;
+use chrono::NaiveDate;
+use openmensa_rust_interface::Canteen;
+use tabled::{
+    builder::Builder,
+    settings::{object::Columns, Modify, Style, Width},
+};
+
+/// Prints the cheapest meals served on the given date.
+///
+/// # Arguments
+///
+/// * `canteens` - The canteens whose menus are scanned.
+/// * `date` - The date of the menus.
+/// * `price_group` - The price group whose prices are compared, meals without one are ignored.
+/// * `count` - The maximum number of meals to print.
+/// * `full_meals_only` - Whether to leave out sides, desserts and drinks.
+/// * `language` - The language used for the output.
+///
+/// # Returns
+///
+/// `Ok(())` if the meals were printed, otherwise returns an error message as a `String`.
+pub async fn cheapest(
+    canteens: &[Canteen],
+    date: NaiveDate,
+    price_group: PriceGroup,
+    count: usize,
+    full_meals_only: bool,
+    language: Language,
+) -> Result<(), String> {
+    let cheapest = menu::cheapest(
+        menu::fetch_all(canteens, date).await,
+        price_group,
+        count,
+        full_meals_only,
+    );
+
+    println!("{}", i18n::date(language, date));
+    if cheapest.is_empty() {
+        println!("{}", tr(language, Message::NoPricedMeals));
+        return Ok(());
+    }
+
+    let mut builder = Builder::default();
+    builder.push_record([
+        String::from("#"),
+        price_group.label(language).to_string(),
+        tr(language, Message::Name).to_string(),
+        tr(language, Message::Category).to_string(),
+        tr(language, Message::Canteen).to_string(),
+    ]);
+    for (rank, (served, price)) in cheapest.iter().enumerate() {
+        builder.push_record([
+            (rank + 1).to_string(),
+            format_price(Some(*price)),
+            served.meal.name.clone(),
+            served.meal.category.clone(),
+            served.canteen.clone(),
+        ]);
+    }
+
+    let mut table = builder.build();
+    table
+        .with(Style::modern())
+        .with(Modify::new(Columns::new(..)).with(Width::wrap(30).keep_words()));
+    println!("{}", table);
+    Ok(())
+}
@@ -1,5 +1,8 @@
 use crate::apis::{meme_api, uselessfact};
+use crate::i18n::{self, tr, Language, Message};
 use crate::models::TabledMeal;
+use crate::ratings::{self, Ratings};
+use crate::storage::Store;
 use chrono::NaiveDate;
 use openmensa_rust_interface::Canteen;
 use tabled::{
@@ -13,21 +16,30 @@ use tabled::{
 ///
 /// * `canteens` - A vector of `Canteen` structs for which meals are to be fetched and printed.
 /// * `date` - The date for which meals are to be fetched (in `NaiveDate` format).
+/// * `language` - The language used for the table headers and messages.
 ///
 /// # Returns
 ///
 /// `Ok(())` if meals are printed successfully, otherwise returns an error message as a `String`.
-pub async fn print_meals(canteens: Vec<Canteen>, date: NaiveDate) -> Result<(), String> {
+pub async fn print_meals(
+    canteens: Vec<Canteen>,
+    date: NaiveDate,
+    language: Language,
+) -> Result<(), String> {
+    let meal_ratings: Store<Ratings> = Store::open(ratings::RATINGS_FILE)?;
+
     for canteen in canteens {
-        match get_meals_for_canteen(&canteen, &date).await {
+        match get_meals_for_canteen(&canteen, &date, &meal_ratings).await {
             Ok(tabled_meals) => {
-                println!("{}", canteen.name);
-                print_table(&tabled_meals);
+                println!("{} · {}", canteen.name, i18n::date(language, date));
+                print_table(&tabled_meals, language);
             }
             Err(err) => {
                 return Err(format!(
-                    "Error fetching meals for {}: {}",
-                    canteen.name, err
+                    "{} ({}): {}",
+                    tr(language, Message::MealsFetchFailed),
+                    canteen.name,
+                    err
                 ));
             }
         }
@@ -41,6 +53,7 @@ pub async fn print_meals(canteens: Vec<Canteen>, date: NaiveDate) -> Result<(),
 ///
 /// * `canteen` - A reference to the `Canteen` struct for which meals are to be fetched.
 /// * `date` - A reference to the `NaiveDate` for which meals are to be fetched.
+/// * `meal_ratings` - The ratings given by bot users, shown next to each meal.
 ///
 /// # Returns
 ///
@@ -49,11 +62,23 @@ pub async fn print_meals(canteens: Vec<Canteen>, date: NaiveDate) -> Result<(),
 async fn get_meals_for_canteen(
     canteen: &Canteen,
     date: &NaiveDate,
+    meal_ratings: &Store<Ratings>,
 ) -> Result<Vec<TabledMeal>, String> {
     let meals = openmensa_rust_interface::get_meals(canteen, &date.to_string())
         .await
         .map_err(|e| e.to_string())?;
-    let tabled_meals: Vec<TabledMeal> = meals.into_iter().map(TabledMeal::from).collect();
+    let tabled_meals: Vec<TabledMeal> = meal_ratings.with(|all_ratings| {
+        meals
+            .into_iter()
+            .map(|meal| {
+                let rating = ratings::format_average(all_ratings, canteen.id, &meal.name);
+                TabledMeal {
+                    rating,
+                    ..TabledMeal::from(meal)
+                }
+            })
+            .collect()
+    });
     Ok(tabled_meals)
 }
 
@@ -62,48 +87,86 @@ async fn get_meals_for_canteen(
 /// # Arguments
 ///
 /// * `tabled_meals` - A slice of `TabledMeal` structs to be printed as a table.
-fn print_table(tabled_meals: &[TabledMeal]) {
-    let mut table = Table::new(tabled_meals);
+/// * `language` - The language used for the table headers.
+fn print_table(tabled_meals: &[TabledMeal], language: Language) {
+    let mut builder = Table::builder(tabled_meals);
+    builder.remove_record(0);
+    builder.insert_record(
+        0,
+        [
+            Message::Name,
+            Message::Students,
+            Message::Employees,
+            Message::Others,
+            Message::Notes,
+            Message::Rating,
+        ]
+        .map(|message| tr(language, message)),
+    );
+
+    let mut table = builder.build();
     table
         .with(Style::modern())
         .with(Modify::new(Columns::first()).with(Width::wrap(10).keep_words()))
-        .with(Modify::new(Columns::last()).with(Width::wrap(10).keep_words()));
+        .with(Modify::new(Columns::single(4)).with(Width::wrap(10).keep_words()));
 
     println!("{}", table);
 }
 
-/// Fetches and prints a meme.
-pub async fn meme() {
-    match meme_api::get().await {
-        Ok(meme) => {
-            println!("{}", meme.url);
+/// Fetches and prints one or more memes.
+///
+/// # Arguments
+///
+/// * `count` - Optional number of memes, fetched in a single batch.
+/// * `language` - The language used for error messages.
+pub async fn meme(count: Option<u32>, language: Language) {
+    let memes = match count {
+        Some(count) => meme_api::batch(None, count).await,
+        None => meme_api::get(None).await.map(|meme| vec![meme]),
+    };
+
+    match memes {
+        Ok(memes) => {
+            for meme in memes {
+                println!("{}", meme.url);
+            }
         }
         Err(err) => {
-            eprintln!("Error fetching meme: {:?}", err);
+            eprintln!("{} {:?}", tr(language, Message::MemeFetchFailed), err);
         }
     }
 }
 
 /// Fetches and prints a daily useless fact.
-pub async fn daily_fact() {
-    match uselessfact::daily(Some(String::from("de"))).await {
+///
+/// # Arguments
+///
+/// * `fact_language` - The language code of the fact.
+/// * `language` - The language used for error messages.
+pub async fn daily_fact(fact_language: String, language: Language) {
+    match uselessfact::daily(Some(fact_language)).await {
         Ok(fact) => {
             println!("{}", fact.text);
         }
         Err(err) => {
-            eprintln!("Error fetching daily fact: {:?}", err);
+            eprintln!("{} {:?}", tr(language, Message::DailyFactFetchFailed), err);
         }
     }
 }
 
 /// Fetches and prints a random useless fact.
-pub async fn random_fact() {
-    match uselessfact::random(Some(String::from("de"))).await {
+///
+/// # Arguments
+///
+/// * `fact_language` - The language code of the fact.
+/// * `language` - The language used for error messages.
+pub async fn random_fact(fact_language: String, language: Language) {
+    match uselessfact::random(Some(fact_language)).await {
         Ok(fact) => {
             println!("{}", fact.text)
         }
         Err(err) => {
-            eprintln!("Error fetching random fact: {:?}", err);
+            eprintln!("{} {:?}", tr(language, Message::RandomFactFetchFailed), err);
         }
     }
 }
@@ -0,0 +1,137 @@
+use crate::i18n::{self, tr, Language, Message};
+use crate::models::comparison::CategoryRow;
+use crate::models::price::format_price;
+use crate::models::{canteen, Comparison, PriceGroup};
+use chrono::NaiveDate;
+use openmensa_rust_interface::Canteen;
+use tabled::{
+    builder::Builder,
+    settings::{object::Columns, Modify, Style, Width},
+};
+
+/// Marker in front of the cheapest meal of a category.
+const CHEAPEST_MARKER: &str = "★";
+
+/// Formats the meals of one canteen in a category, one per line.
+///
+/// # Arguments
+///
+/// * `row` - The category row.
+/// * `canteen` - The index of the canteen.
+/// * `price_group` - The price group whose prices are shown.
+///
+/// # Returns
+///
+/// The meals with their prices, or `-` if the canteen has none in this category.
+fn format_cell(row: &CategoryRow, canteen: usize, price_group: PriceGroup) -> String {
+    let lines: Vec<String> = row.meals[canteen]
+        .iter()
+        .enumerate()
+        .map(|(index, meal)| {
+            let marker = if row.is_cheapest(canteen, index) {
+                format!("{} ", CHEAPEST_MARKER)
+            } else {
+                String::new()
+            };
+            format!(
+                "{}{} ({})",
+                marker,
+                meal.name,
+                format_price(price_group.price(&meal.prices))
+            )
+        })
+        .collect();
+
+    if lines.is_empty() {
+        String::from("-")
+    } else {
+        lines.join("\n")
+    }
+}
+
+/// Prints the menus of several canteens side by side, grouped by category.
+///
+/// Canteens given more than once are only shown once.
+///
+/// # Arguments
+///
+/// * `queries` - The IDs or names of the canteens.
+/// * `date` - The date of the menus.
+/// * `price_group` - The price group used to highlight the cheapest meals.
+/// * `language` - The language used for the output.
+///
+/// # Returns
+///
+/// `Ok(())` if the comparison was printed, otherwise returns an error message as a `String`.
+pub async fn compare(
+    queries: &[String],
+    date: NaiveDate,
+    price_group: PriceGroup,
+    language: Language,
+) -> Result<(), String> {
+    let mut canteens = Vec::new();
+    for query in queries {
+        match canteen::find(query).await {
+            Ok(Some(canteen)) => {
+                if !canteens
+                    .iter()
+                    .any(|known: &Canteen| known.id == canteen.id)
+                {
+                    canteens.push(canteen);
+                }
+            }
+            Ok(None) => {
+                return Err(format!(
+                    "{} ({})",
+                    tr(language, Message::CanteenNotFound),
+                    query
+                ))
+            }
+            Err(err) => {
+                return Err(format!(
+                    "{} {}",
+                    tr(language, Message::CanteenFetchFailed),
+                    err
+                ))
+            }
+        }
+    }
+
+    if canteens.len() < 2 {
+        return Err(tr(language, Message::TooFewCanteens).to_string());
+    }
+
+    let comparison = Comparison::fetch(&canteens, date, price_group)
+        .await
+        .map_err(|err| format!("{} ({})", tr(language, Message::MealsFetchFailed), err))?;
+
+    println!("{}", i18n::date(language, date));
+    if comparison.rows.is_empty() {
+        println!("{}", tr(language, Message::NoMeals));
+        return Ok(());
+    }
+
+    let mut builder = Builder::default();
+    builder.push_record(
+        std::iter::once(String::from(tr(language, Message::Category)))
+            .chain(comparison.canteens.iter().cloned()),
+    );
+    for row in &comparison.rows {
+        builder.push_record(std::iter::once(row.category.clone()).chain(
+            (0..comparison.canteens.len()).map(|canteen| format_cell(row, canteen, price_group)),
+        ));
+    }
+
+    let mut table = builder.build();
+    table
+        .with(Style::modern())
+        .with(Modify::new(Columns::new(..)).with(Width::wrap(30).keep_words()));
+    println!("{}", table);
+    println!(
+        "{} {} {}",
+        CHEAPEST_MARKER,
+        tr(language, Message::CheapestFor),
+        price_group.label(language)
+    );
+    Ok(())
+}
@@ -0,0 +1,529 @@
+use crate::config::{Configs, Problem};
+use crate::i18n::{tr, Language, Message};
+use openmensa_rust_interface::{get_canteen_by_id, get_canteens_by_location, Canteen};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+use std::path::Path;
+use toml_edit::{Array, DocumentMut, ImDocument, Item, Table, TableLike, Value};
+
+/// Commented configuration written by `config init`.
+const TEMPLATE: &str = include_str!("config.template.toml");
+
+/// A problem found in the configuration file.
+struct Diagnostic {
+    /// The byte range of the offending text, if known.
+    span: Option<Range<usize>>,
+    /// Description of the problem.
+    message: String,
+}
+
+impl Diagnostic {
+    /// Formats the problem with the offending line and a marker below it.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the configuration file.
+    /// * `contents` - The contents of the configuration file.
+    ///
+    /// # Returns
+    ///
+    /// The formatted diagnostic.
+    fn render(&self, path: &Path, contents: &str) -> String {
+        let Some(span) = self.span.clone() else {
+            return format!("error: {}\n  --> {}", self.message, path.display());
+        };
+
+        let start = span.start.min(contents.len());
+        let line_start = contents[..start].rfind('\n').map_or(0, |index| index + 1);
+        let line = contents[line_start..]
+            .split('\n')
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('\r');
+        // Spans reaching past the line, even into its `\r`, are cut off at its end
+        let line_end = line_start + line.len();
+        let number = contents[..start].matches('\n').count() + 1;
+        let column = contents[line_start..start].chars().count();
+        let width = contents[start..span.end.min(line_end).max(start)]
+            .chars()
+            .count()
+            .max(1);
+        let gutter = " ".repeat(number.to_string().len());
+
+        format!(
+            "error: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}",
+            self.message,
+            gutter,
+            path.display(),
+            number,
+            column + 1,
+            gutter,
+            number,
+            line,
+            gutter,
+            " ".repeat(column),
+            "^".repeat(width)
+        )
+    }
+}
+
+/// Collects the tables of an array of tables or of an array of inline tables.
+///
+/// # Arguments
+///
+/// * `item` - The array to collect the tables from.
+///
+/// # Returns
+///
+/// The tables, empty if the item is no array.
+fn tables(item: &Item) -> Vec<&dyn TableLike> {
+    if let Some(array) = item.as_array_of_tables() {
+        return array.iter().map(|table| table as &dyn TableLike).collect();
+    }
+    item.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|value| value.as_inline_table())
+        .map(|table| table as &dyn TableLike)
+        .collect()
+}
+
+/// Looks up where a problem found by `Configs::problems` is in the configuration file.
+///
+/// # Arguments
+///
+/// * `document` - The parsed configuration file.
+/// * `problem` - The problem to locate.
+///
+/// # Returns
+///
+/// The byte range of the offending value, if it can be found.
+fn problem_span(document: &ImDocument<&str>, problem: &Problem) -> Option<Range<usize>> {
+    let [parent, array] = problem.table;
+    let item = document
+        .get(parent)
+        .and_then(Item::as_table_like)
+        .and_then(|parent| parent.get(array))?;
+    tables(item)
+        .get(problem.index)
+        .and_then(|table| table.get(problem.key))
+        .and_then(Item::span)
+}
+
+/// Reports configured canteen IDs that do not exist on OpenMensa.
+///
+/// # Arguments
+///
+/// * `document` - The parsed configuration file.
+/// * `diagnostics` - Where found problems are added.
+/// * `language` - The language of the error messages.
+///
+/// # Returns
+///
+/// `Ok(())` if all IDs were looked up, otherwise returns an error message as a `String`.
+async fn canteen_ids(
+    document: &ImDocument<&str>,
+    diagnostics: &mut Vec<Diagnostic>,
+    language: Language,
+) -> Result<(), String> {
+    let ids = document
+        .get("locations")
+        .and_then(Item::as_table_like)
+        .and_then(|locations| locations.get("canteens"))
+        .and_then(Item::as_array);
+
+    for value in ids.into_iter().flatten() {
+        let Some(id) = value.as_integer().and_then(|id| u32::try_from(id).ok()) else {
+            continue;
+        };
+        match get_canteen_by_id(id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => diagnostics.push(Diagnostic {
+                span: value.span(),
+                message: format!("{}: {}", tr(language, Message::CanteenNotOnOpenMensa), id),
+            }),
+            Err(err) => {
+                return Err(format!(
+                    "{} {}",
+                    tr(language, Message::CanteenFetchFailed),
+                    err
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes a commented configuration template.
+///
+/// # Arguments
+///
+/// * `path` - The path of the configuration file to create.
+/// * `force` - Whether to overwrite an existing file.
+/// * `language` - The language of the output.
+///
+/// # Returns
+///
+/// `Ok(())` if the file was written, otherwise returns an error message as a `String`.
+pub fn init(path: &Path, force: bool, language: Language) -> Result<(), String> {
+    if path.exists() && !force {
+        return Err(format!(
+            "{} {}",
+            tr(language, Message::ConfigExists),
+            path.display()
+        ));
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            format!(
+                "{} {}: {}",
+                tr(language, Message::CreateFailed),
+                parent.display(),
+                err
+            )
+        })?;
+    }
+    fs::write(path, TEMPLATE).map_err(|err| {
+        format!(
+            "{} {}: {}",
+            tr(language, Message::WriteFailed),
+            path.display(),
+            err
+        )
+    })?;
+
+    println!(
+        "{} {}",
+        tr(language, Message::ConfigCreated),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Validates the configuration file and prints every problem found.
+///
+/// Checks the TOML syntax, unknown keys, the types of all values, the values that
+/// `Configs::validate` rejects and whether the configured canteen IDs exist on OpenMensa.
+///
+/// # Arguments
+///
+/// * `path` - The path of the configuration file.
+/// * `language` - The language of the output.
+///
+/// # Returns
+///
+/// `Ok(())` if the configuration is valid, otherwise returns an error message as a `String`.
+pub async fn check(path: &Path, language: Language) -> Result<(), String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("{}: {}", tr(language, Message::ConfigReadFailed), err))?;
+
+    let mut diagnostics = Vec::new();
+    match ImDocument::parse(contents.as_str()) {
+        Ok(document) => {
+            // Unknown keys and wrong types are reported by serde with the span of the value.
+            // Values and canteens are only checked once the whole file deserializes.
+            match toml::from_str::<Configs>(&contents) {
+                Ok(configs) => {
+                    for problem in configs.problems() {
+                        diagnostics.push(Diagnostic {
+                            span: problem_span(&document, &problem),
+                            message: problem.message,
+                        });
+                    }
+                    canteen_ids(&document, &mut diagnostics, language).await?;
+                }
+                Err(err) => diagnostics.push(Diagnostic {
+                    span: err.span(),
+                    message: err.message().trim_end().to_string(),
+                }),
+            }
+        }
+        Err(err) => diagnostics.push(Diagnostic {
+            span: err.span(),
+            message: err.message().trim_end().to_string(),
+        }),
+    }
+
+    if diagnostics.is_empty() {
+        println!("{}", tr(language, Message::ConfigValid));
+        return Ok(());
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.span.as_ref().map(|span| span.start));
+    for diagnostic in &diagnostics {
+        eprintln!("{}\n", diagnostic.render(path, &contents));
+    }
+    Err(format!(
+        "{}: {}",
+        tr(language, Message::ConfigProblems),
+        diagnostics.len()
+    ))
+}
+
+/// Asks the user which of the listed canteens to add.
+///
+/// # Arguments
+///
+/// * `canteens` - The canteens to choose from.
+/// * `language` - The language of the prompt.
+///
+/// # Returns
+///
+/// The chosen canteens, or an error message as a `String` if reading the input fails.
+fn pick_canteens(canteens: &[Canteen], language: Language) -> Result<Vec<&Canteen>, String> {
+    for (index, canteen) in canteens.iter().enumerate() {
+        println!(
+            "  [{}] {} (ID {}), {}",
+            index + 1,
+            canteen.name,
+            canteen.id,
+            canteen.address
+        );
+    }
+
+    let stdin = io::stdin();
+    loop {
+        print!("{} ", tr(language, Message::SelectCanteens));
+        io::stdout().flush().map_err(|err| err.to_string())?;
+
+        let mut input = String::new();
+        if stdin
+            .lock()
+            .read_line(&mut input)
+            .map_err(|err| err.to_string())?
+            == 0
+        {
+            // End of input, nothing more can be selected
+            return Ok(Vec::new());
+        }
+
+        let input = input.trim();
+        if input.eq_ignore_ascii_case("all") {
+            return Ok(canteens.iter().collect());
+        }
+
+        let selection: Option<Vec<&Canteen>> = input
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .map(|token| {
+                token
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|number| canteens.get(number.checked_sub(1)?))
+            })
+            .collect();
+
+        match selection {
+            Some(selection) => return Ok(selection),
+            None => eprintln!("{}", tr(language, Message::InvalidSelection)),
+        }
+    }
+}
+
+/// Adds canteen IDs to `locations.canteens` of a TOML document.
+///
+/// Existing IDs, comments and formatting are kept.
+///
+/// # Arguments
+///
+/// * `document` - The parsed configuration file.
+/// * `ids` - The IDs to add.
+///
+/// # Returns
+///
+/// The number of IDs that were not in the list yet, or an error message as a `String`.
+fn add_canteen_ids(document: &mut DocumentMut, ids: &[u32]) -> Result<usize, String> {
+    let locations = document
+        .entry("locations")
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_like_mut()
+        .ok_or("`locations` is not a table")?;
+    let canteens = locations
+        .entry("canteens")
+        .or_insert(Item::Value(Value::Array(Array::new())))
+        .as_array_mut()
+        .ok_or("`locations.canteens` is not an array")?;
+
+    let mut added = 0;
+    for id in ids {
+        let exists = canteens
+            .iter()
+            .any(|value| value.as_integer() == Some(i64::from(*id)));
+        if !exists {
+            canteens.push(i64::from(*id));
+            added += 1;
+        }
+    }
+    Ok(added)
+}
+
+/// Finds canteens in the configured cities and writes the chosen IDs into the config file.
+///
+/// # Arguments
+///
+/// * `configs` - The loaded configuration.
+/// * `path` - The path of the configuration file to update.
+/// * `all` - Whether to add all found canteens without asking.
+///
+/// # Returns
+///
+/// `Ok(())` if the config file was updated, otherwise returns an error message as a `String`.
+pub async fn discover(configs: &Configs, path: &Path, all: bool) -> Result<(), String> {
+    let language = configs.language;
+    if configs.locations.cities.is_empty() {
+        return Err(tr(language, Message::NoCities).to_string());
+    }
+
+    let mut ids = Vec::new();
+    for city in &configs.locations.cities {
+        let canteens = get_canteens_by_location(city).await.map_err(|err| {
+            format!(
+                "{} ({}): {}",
+                tr(language, Message::CanteenFetchFailed),
+                city,
+                err
+            )
+        })?;
+
+        println!("{} {}:", tr(language, Message::CanteensIn), city);
+        if canteens.is_empty() {
+            println!("  {}", tr(language, Message::NoCanteens));
+            continue;
+        }
+
+        let chosen = if all {
+            canteens.iter().collect()
+        } else {
+            pick_canteens(&canteens, language)?
+        };
+        ids.extend(chosen.iter().map(|canteen| canteen.id));
+    }
+
+    let contents = if path.exists() {
+        fs::read_to_string(path)
+            .map_err(|err| format!("{}: {}", tr(language, Message::ConfigReadFailed), err))?
+    } else {
+        String::new()
+    };
+    let mut document: DocumentMut = contents
+        .parse()
+        .map_err(|err| format!("{}: {}", tr(language, Message::ConfigParseFailed), err))?;
+
+    let added = add_canteen_ids(&mut document, &ids)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            format!(
+                "{} {}: {}",
+                tr(language, Message::CreateFailed),
+                parent.display(),
+                err
+            )
+        })?;
+    }
+    fs::write(path, document.to_string()).map_err(|err| {
+        format!(
+            "{} {}: {}",
+            tr(language, Message::WriteFailed),
+            path.display(),
+            err
+        )
+    })?;
+
+    println!(
+        "{} {}: {}",
+        tr(language, Message::CanteensAdded),
+        path.display(),
+        added
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Renders a diagnostic for the given span of the contents.
+    fn render(contents: &str, span: Option<Range<usize>>) -> String {
+        Diagnostic {
+            span,
+            message: String::from("problem"),
+        }
+        .render(Path::new("config.toml"), contents)
+    }
+
+    #[test]
+    fn render_counts_columns_in_characters() {
+        let contents = "[mensa]\nkäse = \"x\"\n";
+        let start = contents.find('"').unwrap();
+
+        assert_eq!(
+            render(contents, Some(start..start + 3)),
+            "error: problem\n --> config.toml:2:8\n  |\n2 | käse = \"x\"\n  |        ^^^"
+        );
+    }
+
+    #[test]
+    fn render_cuts_spans_at_the_end_of_the_line() {
+        let contents = "a = 1\r\nb = [\r\n  2,\r\n]\r\n";
+        let start = contents.find('[').unwrap();
+
+        assert_eq!(
+            render(contents, Some(start..contents.len())),
+            "error: problem\n --> config.toml:2:5\n  |\n2 | b = [\n  |     ^"
+        );
+    }
+
+    #[test]
+    fn render_marks_spans_at_the_end_of_the_file() {
+        let contents = "a = ";
+
+        assert_eq!(
+            render(contents, Some(4..10)),
+            "error: problem\n --> config.toml:1:5\n  |\n1 | a = \n  |     ^"
+        );
+    }
+
+    #[test]
+    fn render_without_span_names_the_file() {
+        assert_eq!(render("a = 1\n", None), "error: problem\n  --> config.toml");
+    }
+
+    #[test]
+    fn add_canteen_ids_keeps_existing_ids_and_comments() {
+        let mut document: DocumentMut = "# Where to eat\n[locations]\n\
+            cities = [\"Köln\"] # home\n\
+            canteens = [\n    1, # Mensa Nord\n    2,\n]\n"
+            .parse()
+            .unwrap();
+
+        let added = add_canteen_ids(&mut document, &[2, 3, 1, 4]).unwrap();
+
+        assert_eq!(added, 2);
+        let canteens: Vec<i64> = document["locations"]["canteens"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(Value::as_integer)
+            .collect();
+        assert_eq!(canteens, vec![1, 2, 3, 4]);
+        let written = document.to_string();
+        for comment in ["# Where to eat", "# home", "# Mensa Nord"] {
+            assert!(
+                written.contains(comment),
+                "{} missing in {}",
+                comment,
+                written
+            );
+        }
+    }
+
+    #[test]
+    fn add_canteen_ids_creates_the_list() {
+        let mut document = DocumentMut::new();
+
+        assert_eq!(add_canteen_ids(&mut document, &[5, 5]).unwrap(), 1);
+        assert_eq!(document.to_string(), "[locations]\ncanteens = [5]\n");
+    }
+}
@@ -0,0 +1,66 @@
+use crate::i18n::{self, tr, Language, Message};
+use crate::models::menu::{self, ServedMeal};
+use crate::models::price::format_prices;
+use chrono::NaiveDate;
+use openmensa_rust_interface::Canteen;
+use tabled::{
+    builder::Builder,
+    settings::{object::Columns, Modify, Style, Width},
+};
+
+/// Prints where a dish is served on the given date.
+///
+/// # Arguments
+///
+/// * `canteens` - The canteens whose menus are searched.
+/// * `dish` - The text to look for in meal names, matched case- and umlaut-insensitively.
+/// * `date` - The date of the menus.
+/// * `language` - The language used for the output.
+///
+/// # Returns
+///
+/// `Ok(())` if the results were printed, otherwise returns an error message as a `String`.
+pub async fn find(
+    canteens: &[Canteen],
+    dish: &str,
+    date: NaiveDate,
+    language: Language,
+) -> Result<(), String> {
+    let matches: Vec<ServedMeal> = menu::fetch_all(canteens, date)
+        .await
+        .into_iter()
+        .filter(|served| menu::matches(&served.meal.name, dish))
+        .collect();
+
+    println!("{}", i18n::date(language, date));
+    if matches.is_empty() {
+        println!("{}", tr(language, Message::DishNotFound));
+        return Ok(());
+    }
+
+    let mut builder = Builder::default();
+    builder.push_record(
+        [
+            Message::Canteen,
+            Message::Name,
+            Message::Prices,
+            Message::Notes,
+        ]
+        .map(|message| tr(language, message)),
+    );
+    for served in &matches {
+        builder.push_record([
+            served.canteen.clone(),
+            served.meal.name.clone(),
+            format_prices(&served.meal.prices, language),
+            served.meal.notes.join(", "),
+        ]);
+    }
+
+    let mut table = builder.build();
+    table
+        .with(Style::modern())
+        .with(Modify::new(Columns::new(..)).with(Width::wrap(30).keep_words()));
+    println!("{}", table);
+    Ok(())
+}
@@ -1,3 +1,9 @@
+pub mod canteens;
+pub mod cheapest;
 pub mod cli;
+pub mod compare;
+pub mod config;
+pub mod find;
+pub mod token;
 
 pub use cli::{daily_fact, meme, print_meals, random_fact};
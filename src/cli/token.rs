@@ -0,0 +1,31 @@
+use crate::config::Configs;
+use crate::secret::{self, Secret};
+
+/// Prints which source the bot token is taken from and whether it looks valid.
+///
+/// The token itself is never printed.
+///
+/// # Arguments
+///
+/// * `flag` - The token passed with `--token`.
+/// * `env_file` - The path passed with `--env-file`.
+/// * `configs` - The loaded configuration.
+///
+/// # Returns
+///
+/// `Ok(())` if a token with a valid format was found, otherwise returns an error message as a `String`.
+pub fn check(
+    flag: Option<&Secret>,
+    env_file: Option<&str>,
+    configs: &Configs,
+) -> Result<(), String> {
+    let (token, source) =
+        secret::resolve_token(flag, env_file, configs.bot.token.as_ref(), |name| {
+            std::env::var(name).ok()
+        })?;
+    println!("Token source: {}", source);
+
+    let bot_id = secret::check_token_format(&token)?;
+    println!("Token format: valid (bot user ID {})", bot_id);
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::time::Duration;
+
+/// Timezone used if none is configured, as OpenMensa mostly lists German canteens.
+pub const DEFAULT_TIMEZONE: Tz = chrono_tz::Europe::Berlin;
+
+/// Returns the current wall-clock time in a timezone.
+///
+/// # Arguments
+///
+/// * `timezone` - The timezone to get the time in.
+///
+/// # Returns
+///
+/// The current local date and time of the timezone.
+pub fn now(timezone: Tz) -> NaiveDateTime {
+    Utc::now().with_timezone(&timezone).naive_local()
+}
+
+/// Returns the current date in a timezone.
+///
+/// # Arguments
+///
+/// * `timezone` - The timezone to get the date in.
+///
+/// # Returns
+///
+/// Today's date in the timezone.
+pub fn today(timezone: Tz) -> NaiveDate {
+    now(timezone).date()
+}
+
+/// Returns how long it is until a wall-clock time in a timezone.
+///
+/// The difference is taken in UTC, so days with a DST change are handled correctly.
+/// A time that is skipped when the clocks are set forward is moved an hour later.
+///
+/// # Arguments
+///
+/// * `timezone` - The timezone of the wall-clock time.
+/// * `local` - The wall-clock time.
+///
+/// # Returns
+///
+/// The time left, zero if the time has already passed.
+pub fn until(timezone: Tz, local: NaiveDateTime) -> Duration {
+    let at = timezone.from_local_datetime(&local).earliest().or_else(|| {
+        timezone
+            .from_local_datetime(&(local + TimeDelta::hours(1)))
+            .earliest()
+    });
+
+    at.and_then(|at| (at.with_timezone(&Utc) - Utc::now()).to_std().ok())
+        .unwrap_or_default()
+}
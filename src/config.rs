@@ -1,9 +1,536 @@
+use crate::apis::uselessfact;
+use crate::clock::DEFAULT_TIMEZONE;
+use crate::i18n::{tr, Language, Message};
 use crate::models::Location;
+use crate::secret::Secret;
+use chrono::NaiveTime;
+use chrono_tz::Tz;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Prefix of environment variables that override configuration values.
+///
+/// Nested keys are separated by a double underscore, e.g. `DISCORD_BOT__FACTS__LANGUAGE`.
+const ENV_PREFIX: &str = "DISCORD_BOT__";
 
 /// Struct representing configurations loaded from a file.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct Configs {
     /// Configuration for locations.
+    #[serde(default)]
     pub locations: Location,
+    /// Configuration for the Discord bot.
+    #[serde(default)]
+    pub bot: BotConfig,
+    /// Default language for replies and CLI output.
+    #[serde(default)]
+    pub language: Language,
+    /// Timezone that decides what "today" is and when announcements are posted
+    /// (defaults to Europe/Berlin).
+    pub timezone: Option<Tz>,
+    /// Configuration for useless facts.
+    #[serde(default)]
+    pub facts: FactsConfig,
+    /// Content filters for memes.
+    #[serde(default)]
+    pub memes: MemesConfig,
+    /// Per-guild settings, keyed by guild ID.
+    #[serde(default)]
+    pub guilds: HashMap<String, GuildConfig>,
+}
+
+impl Configs {
+    /// Looks up the settings of a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The ID of the guild.
+    ///
+    /// # Returns
+    ///
+    /// The guild settings, or `None` if the guild is not configured.
+    pub fn guild(&self, guild_id: u64) -> Option<&GuildConfig> {
+        self.guilds.get(&guild_id.to_string())
+    }
+
+    /// Resolves and validates the language of useless facts.
+    ///
+    /// An explicitly requested language wins, followed by the configured fact language
+    /// and the language used for replies.
+    ///
+    /// # Arguments
+    ///
+    /// * `requested` - The language chosen by the user or an announcement, if any.
+    /// * `language` - The language used for replies and error messages.
+    ///
+    /// # Returns
+    ///
+    /// The language code, or a localized error message if the language is not supported.
+    pub fn fact_language(
+        &self,
+        requested: Option<&str>,
+        language: Language,
+    ) -> Result<String, String> {
+        let code = requested
+            .or(self.facts.language.as_deref())
+            .unwrap_or(language.code())
+            .to_lowercase();
+
+        if uselessfact::is_supported_language(&code) {
+            Ok(code)
+        } else {
+            Err(format!(
+                "{}: \"{}\". {}: {}",
+                tr(language, Message::UnsupportedLanguage),
+                code,
+                tr(language, Message::SupportedLanguages),
+                uselessfact::LANGUAGES.join(", ")
+            ))
+        }
+    }
+
+    /// Resolves the canteens searched in a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The ID of the guild, `None` outside of guilds.
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the guild's canteens, falling back to `locations.canteens`.
+    pub fn canteens_for(&self, guild_id: Option<u64>) -> &[u32] {
+        guild_id
+            .and_then(|guild_id| self.guild(guild_id))
+            .and_then(|guild| guild.canteens.as_deref())
+            .unwrap_or(&self.locations.canteens)
+    }
+
+    /// Resolves the timezone of a guild.
+    ///
+    /// # Arguments
+    ///
+    /// * `guild_id` - The ID of the guild, `None` for direct messages, the CLI and announcements.
+    ///
+    /// # Returns
+    ///
+    /// The guild's timezone, falling back to the configured and then the default timezone.
+    pub fn timezone_for(&self, guild_id: Option<u64>) -> Tz {
+        guild_id
+            .and_then(|guild_id| self.guild(guild_id))
+            .and_then(|guild| guild.timezone)
+            .or(self.timezone)
+            .unwrap_or(DEFAULT_TIMEZONE)
+    }
+
+    /// Finds values that parse fine but can not be used, like malformed announcement times.
+    ///
+    /// # Returns
+    ///
+    /// The problems, in the order of the configuration.
+    pub fn problems(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        for (index, announcement) in self.facts.announcements.iter().enumerate() {
+            let Some(time) = &announcement.time else {
+                continue;
+            };
+            if let Err(err) = NaiveTime::parse_from_str(time, "%H:%M") {
+                problems.push(Problem {
+                    table: ["facts", "announcements"],
+                    index,
+                    key: "time",
+                    message: format!(
+                        "Invalid announcement time \"{}\" for channel {}: {}",
+                        time, announcement.channel, err
+                    ),
+                });
+            }
+        }
+
+        for (index, coordinate) in self.locations.coordinates.iter().flatten().enumerate() {
+            for (key, value, limit) in [
+                ("latitude", coordinate.latitude, 90.0),
+                ("longitude", coordinate.longitude, 180.0),
+            ] {
+                if !(-limit..=limit).contains(&value) {
+                    problems.push(Problem {
+                        table: ["locations", "coordinates"],
+                        index,
+                        key,
+                        message: format!(
+                            "`{}` of {} must be between -{} and {}",
+                            key, coordinate.city, limit, limit
+                        ),
+                    });
+                }
+            }
+        }
+        problems
+    }
+
+    /// Checks values that parse fine but can not be used, like malformed announcement times.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the configuration can be used, otherwise returns `Err(String)`.
+    pub fn validate(&self) -> Result<(), String> {
+        match self.problems().into_iter().next() {
+            Some(problem) => Err(problem.message),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A value that parses fine but can not be used.
+#[derive(Debug, PartialEq)]
+pub struct Problem {
+    /// The path of the array of tables holding the value, like `locations.coordinates`.
+    pub table: [&'static str; 2],
+    /// The position of the table holding the value within the array.
+    pub index: usize,
+    /// The key of the value.
+    pub key: &'static str,
+    /// Description of the problem.
+    pub message: String,
+}
+
+/// Configuration for the Discord bot.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BotConfig {
+    /// IDs of development guilds to register commands in instead of registering them globally.
+    #[serde(default)]
+    pub guilds: Vec<u64>,
+    /// The Discord bot token, used if no other token source is set.
+    pub token: Option<Secret>,
+}
+
+/// Configuration for useless facts.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FactsConfig {
+    /// Default language of facts, falls back to the reply language if not set.
+    pub language: Option<String>,
+    /// Channels that get the fact of the day posted every day.
+    #[serde(default)]
+    pub announcements: Vec<Announcement>,
+}
+
+/// A daily fact-of-the-day announcement in a channel.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Announcement {
+    /// The ID of the channel to post into.
+    pub channel: u64,
+    /// Language of the fact, falls back to the default fact language if not set.
+    pub language: Option<String>,
+    /// Local time of day to post at in `HH:MM` format (defaults to "08:00").
+    pub time: Option<String>,
+}
+
+/// Content filters for memes.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MemesConfig {
+    /// How memes marked as NSFW are handled.
+    #[serde(default = "default_nsfw_policy")]
+    pub nsfw: ContentPolicy,
+    /// How memes marked as spoilers are handled.
+    #[serde(default = "default_spoiler_policy")]
+    pub spoiler: ContentPolicy,
+    /// Subreddits memes may come from, any subreddit is allowed if empty.
+    #[serde(default)]
+    pub allow_subreddits: Vec<String>,
+    /// Subreddits memes must never come from.
+    #[serde(default)]
+    pub deny_subreddits: Vec<String>,
+}
+
+impl Default for MemesConfig {
+    fn default() -> Self {
+        MemesConfig {
+            nsfw: default_nsfw_policy(),
+            spoiler: default_spoiler_policy(),
+            allow_subreddits: Vec::new(),
+            deny_subreddits: Vec::new(),
+        }
+    }
+}
+
+impl MemesConfig {
+    /// Checks whether memes from a subreddit may be posted.
+    ///
+    /// # Arguments
+    ///
+    /// * `subreddit` - The name of the subreddit, compared case-insensitively.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the subreddit is not denied and, if an allow list is set, on it.
+    pub fn allows_subreddit(&self, subreddit: &str) -> bool {
+        let matches = |name: &String| name.eq_ignore_ascii_case(subreddit);
+
+        !self.deny_subreddits.iter().any(matches)
+            && (self.allow_subreddits.is_empty() || self.allow_subreddits.iter().any(matches))
+    }
+}
+
+/// How memes with a content flag are handled.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentPolicy {
+    /// Post the meme as it is.
+    Allow,
+    /// Post the meme in NSFW channels only and fetch another one elsewhere.
+    NsfwChannels,
+    /// Post the meme hidden behind Discord spoiler formatting.
+    Spoiler,
+    /// Never post the meme and fetch another one.
+    Refetch,
+}
+
+/// Default policy for NSFW memes.
+fn default_nsfw_policy() -> ContentPolicy {
+    ContentPolicy::NsfwChannels
+}
+
+/// Default policy for spoiler memes.
+fn default_spoiler_policy() -> ContentPolicy {
+    ContentPolicy::Spoiler
+}
+
+/// Settings that apply to a single guild.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct GuildConfig {
+    /// Language for replies in this guild, overriding the default language.
+    pub language: Option<Language>,
+    /// Timezone of this guild, overriding the configured timezone.
+    pub timezone: Option<Tz>,
+    /// IDs of the canteens searched in this guild, replacing `locations.canteens`.
+    pub canteens: Option<Vec<u32>>,
+    /// Meme filters for this guild, replacing the global ones.
+    pub memes: Option<MemesConfig>,
+}
+
+/// Returns the default path of the configuration file.
+///
+/// Honours `$XDG_CONFIG_HOME` and falls back to `~/.config/discord-bot/config.toml`.
+///
+/// # Returns
+///
+/// The path of the configuration file (it does not have to exist).
+pub fn default_path() -> PathBuf {
+    match std::env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.is_empty() => Path::new(&dir).join("discord-bot").join("config.toml"),
+        _ => PathBuf::from(shellexpand::tilde("~/.config/discord-bot/config.toml").into_owned()),
+    }
+}
+
+/// Resolves the path of the configuration file.
+///
+/// # Arguments
+///
+/// * `explicit` - The path passed with `--config`, if any.
+///
+/// # Returns
+///
+/// The explicit path with `~` expanded, otherwise the default path.
+pub fn resolve_path(explicit: Option<&str>) -> PathBuf {
+    match explicit {
+        Some(path) => PathBuf::from(shellexpand::tilde(path).into_owned()),
+        None => default_path(),
+    }
+}
+
+/// Parses the value of an environment override.
+///
+/// Values that are valid TOML, like numbers, booleans or arrays, keep their type.
+/// Everything else is taken as a plain string.
+///
+/// # Arguments
+///
+/// * `raw` - The value of the environment variable.
+///
+/// # Returns
+///
+/// The parsed TOML value.
+fn parse_env_value(raw: &str) -> Value {
+    toml::from_str::<Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+/// Applies `DISCORD_BOT__*` environment overrides to a parsed configuration.
+///
+/// # Arguments
+///
+/// * `table` - The parsed configuration file.
+/// * `vars` - The environment variables as name and value pairs.
+///
+/// # Returns
+///
+/// `Ok(())` if all overrides were applied, otherwise returns `Err(String)`.
+fn apply_env_overrides(
+    table: &mut Table,
+    vars: impl Iterator<Item = (String, String)>,
+) -> Result<(), String> {
+    for (name, raw) in vars {
+        let Some(path) = name.strip_prefix(ENV_PREFIX) else {
+            continue;
+        };
+
+        let keys: Vec<String> = path.split("__").map(str::to_lowercase).collect();
+        let Some((last, parents)) = keys
+            .split_last()
+            .filter(|_| keys.iter().all(|key| !key.is_empty()))
+        else {
+            return Err(format!("Invalid configuration override: {}", name));
+        };
+
+        let mut current = &mut *table;
+        for key in parents {
+            current = current
+                .entry(key.clone())
+                .or_insert_with(|| Value::Table(Table::new()))
+                .as_table_mut()
+                .ok_or_else(|| format!("Can not apply {}: `{}` is not a table", name, key))?;
+        }
+        current.insert(last.clone(), parse_env_value(&raw));
+    }
+    Ok(())
+}
+
+/// Loads the configuration from built-in defaults, the config file and the environment.
+///
+/// Later layers win: values from the file replace the defaults and
+/// `DISCORD_BOT__*` environment variables replace values from the file.
+///
+/// # Arguments
+///
+/// * `path` - The path of the TOML configuration file.
+/// * `required` - Whether a missing file is an error, e.g. because it was passed with `--config`.
+///
+/// # Returns
+///
+/// Returns `Ok(Configs)` if the configuration could be loaded, otherwise returns `Err(String)`.
+pub fn load(path: &Path, required: bool) -> Result<Configs, String> {
+    let contents = if path.exists() || required {
+        fs::read_to_string(path).map_err(|err| format!("Error reading config file: {}", err))?
+    } else {
+        String::new()
+    };
+
+    let overrides: Vec<(String, String)> = std::env::vars()
+        .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+        .collect();
+    if overrides.is_empty() {
+        // Parsing the file directly keeps line numbers in error messages
+        return toml::from_str(&contents)
+            .map_err(|err| format!("Failed to parse the TOML: {}", err));
+    }
+
+    let mut table: Table =
+        toml::from_str(&contents).map_err(|err| format!("Failed to parse the TOML: {}", err))?;
+    apply_env_overrides(&mut table, overrides.into_iter())?;
+
+    Configs::deserialize(Value::Table(table))
+        .map_err(|err| format!("Invalid configuration: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds override variables from name and value pairs.
+    fn vars(pairs: &[(&str, &str)]) -> impl Iterator<Item = (String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn overrides_replace_nested_values() {
+        let mut table: Table = toml::from_str("[facts]\nlanguage = \"de\"\n").unwrap();
+        apply_env_overrides(&mut table, vars(&[("DISCORD_BOT__FACTS__LANGUAGE", "en")])).unwrap();
+
+        assert_eq!(table["facts"]["language"].as_str(), Some("en"));
+    }
+
+    #[test]
+    fn overrides_create_missing_tables() {
+        let mut table = Table::new();
+        apply_env_overrides(&mut table, vars(&[("DISCORD_BOT__BOT__GUILDS", "[1, 2]")])).unwrap();
+
+        let guilds = table["bot"]["guilds"].as_array().unwrap();
+        assert_eq!(guilds, &vec![Value::Integer(1), Value::Integer(2)]);
+    }
+
+    #[test]
+    fn overrides_keep_toml_types_and_fall_back_to_strings() {
+        let mut table = Table::new();
+        apply_env_overrides(
+            &mut table,
+            vars(&[
+                ("DISCORD_BOT__LOCATIONS__CANTEENS", "[78]"),
+                ("DISCORD_BOT__TIMEZONE", "Europe/Vienna"),
+            ]),
+        )
+        .unwrap();
+
+        assert!(table["locations"]["canteens"].is_array());
+        assert_eq!(table["timezone"].as_str(), Some("Europe/Vienna"));
+    }
+
+    #[test]
+    fn overrides_ignore_other_variables() {
+        let mut table = Table::new();
+        apply_env_overrides(&mut table, vars(&[("DISCORD_TOKEN", "secret")])).unwrap();
+
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn overrides_reject_empty_keys() {
+        let mut table = Table::new();
+        let result = apply_env_overrides(&mut table, vars(&[("DISCORD_BOT__FACTS____X", "1")]));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn overrides_reject_values_below_scalars() {
+        let mut table: Table = toml::from_str("language = \"de\"\n").unwrap();
+        let result = apply_env_overrides(&mut table, vars(&[("DISCORD_BOT__LANGUAGE__X", "1")]));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn problems_point_at_unusable_values() {
+        let configs: Configs = toml::from_str(
+            "[[facts.announcements]]\nchannel = 1\ntime = \"8 Uhr\"\n\n\
+             [[locations.coordinates]]\ncity = \"Köln\"\nlatitude = 50.9\nlongitude = 181.0\n",
+        )
+        .unwrap();
+
+        let problems = configs.problems();
+
+        assert_eq!(problems.len(), 2);
+        assert_eq!(
+            (problems[0].table, problems[0].index, problems[0].key),
+            (["facts", "announcements"], 0, "time")
+        );
+        assert_eq!(
+            (problems[1].table, problems[1].index, problems[1].key),
+            (["locations", "coordinates"], 0, "longitude")
+        );
+        assert_eq!(configs.validate(), Err(problems[0].message.clone()));
+    }
 }
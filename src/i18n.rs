@@ -0,0 +1,642 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Languages the bot and the CLI can reply in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// English.
+    En,
+    /// German, the language of the CLI before translations were added.
+    #[default]
+    De,
+}
+
+impl Language {
+    /// Parses a language code or a Discord locale like `de` or `en-US`.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The language code or locale.
+    ///
+    /// # Returns
+    ///
+    /// The matching language, or `None` if the language is not supported.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let primary = code.split(['-', '_']).next().unwrap_or_default();
+        match primary.to_lowercase().as_str() {
+            "en" => Some(Language::En),
+            "de" => Some(Language::De),
+            _ => None,
+        }
+    }
+
+    /// Returns the ISO 639-1 code of the language.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::De => "de",
+        }
+    }
+}
+
+/// Messages shown to users by the bot and the CLI.
+#[derive(Clone, Copy, Debug)]
+pub enum Message {
+    /// No canteen matches the given name.
+    CanteenNotFound,
+    /// A meal is not on today's menu of the canteen.
+    MealNotOnMenu,
+    /// Canteens could not be fetched from OpenMensa.
+    CanteenFetchFailed,
+    /// Meals could not be fetched from OpenMensa.
+    MealsFetchFailed,
+    /// A canteen serves no meals on the requested day.
+    NoMeals,
+    /// Label of a meal category.
+    Category,
+    /// Label of meal prices.
+    Prices,
+    /// Label of meal notes.
+    Notes,
+    /// Name of the student price group.
+    Students,
+    /// Name of the employee price group.
+    Employees,
+    /// Name of the pupil price group.
+    Pupils,
+    /// Name of the guest price group.
+    Others,
+    /// Label of a name.
+    Name,
+    /// Label of a meal rating.
+    Rating,
+    /// Label of the newest review of a meal.
+    LatestReview,
+    /// A rating was saved.
+    RatingSaved,
+    /// A dish alert was added.
+    AlertAdded,
+    /// Dish alerts were removed.
+    AlertRemoved,
+    /// No dish alert has the given pattern.
+    AlertNotFound,
+    /// Introduction of a dish alert notification.
+    AlertMatched,
+    /// A user has no dish alerts.
+    NoAlerts,
+    /// A user has reached the maximum number of dish alerts.
+    TooManyAlerts,
+    /// An alert pattern is no valid regular expression.
+    InvalidPattern,
+    /// An alert pattern is blank.
+    EmptyPattern,
+    /// An alert needs a canteen because none are configured.
+    AlertNeedsCanteen,
+    /// Placeholder for alerts that watch the configured canteens.
+    ConfiguredCanteens,
+    /// Title of a lunch meetup.
+    LunchAt,
+    /// Label of the attendees of a lunch meetup.
+    Attendees,
+    /// Nobody joined a lunch meetup yet.
+    NoAttendees,
+    /// Label of today's menu.
+    Menu,
+    /// Button to join a lunch meetup.
+    Join,
+    /// Button to leave a lunch meetup.
+    Leave,
+    /// Reminder that a lunch meetup starts soon.
+    LunchReminder,
+    /// The sign-up of a lunch meetup is closed.
+    LunchClosed,
+    /// A time is not in the `HH:MM` format.
+    InvalidTime,
+    /// A time has already passed today.
+    TimeInPast,
+    /// Label of the city of a canteen.
+    City,
+    /// Label of the address of a canteen.
+    Address,
+    /// Label of the coordinates of a canteen.
+    Coordinates,
+    /// Label of a map link.
+    Map,
+    /// Label of the opening days of the coming week.
+    OpeningDays,
+    /// A canteen is open on a day.
+    Open,
+    /// A canteen is closed on a day.
+    Closed,
+    /// Whether a canteen is open is unknown.
+    Unknown,
+    /// A search found no canteens.
+    NoCanteens,
+    /// No cities are configured.
+    NoCities,
+    /// No canteens are configured or selected for the CLI.
+    NoCanteensConfigured,
+    /// Heading of the canteens found in a city.
+    CanteensIn,
+    /// Prompt to pick canteens from a list.
+    SelectCanteens,
+    /// The picked canteens are not in the list.
+    InvalidSelection,
+    /// Canteen IDs were added to the config file.
+    CanteensAdded,
+    /// The configuration template was written.
+    ConfigCreated,
+    /// The config file already exists.
+    ConfigExists,
+    /// The config file has no problems.
+    ConfigValid,
+    /// The config file has problems.
+    ConfigProblems,
+    /// A configured canteen ID is unknown to OpenMensa.
+    CanteenNotOnOpenMensa,
+    /// A directory could not be created.
+    CreateFailed,
+    /// A file could not be written.
+    WriteFailed,
+    /// The configuration file could not be read.
+    ConfigReadFailed,
+    /// The configuration file is no valid TOML.
+    ConfigParseFailed,
+    /// Label of the price group the cheapest meals are chosen for.
+    CheapestFor,
+    /// Title of a menu comparison.
+    Comparison,
+    /// A comparison needs at least two canteens.
+    TooFewCanteens,
+    /// Label of a canteen.
+    Canteen,
+    /// No canteen serves a matching dish.
+    DishNotFound,
+    /// A dish search is blank.
+    EmptyQuery,
+    /// Title of dish search results.
+    MatchesFor,
+    /// A server has no canteens configured.
+    NoGuildCanteens,
+    /// Summary of results that were left out.
+    MoreResults,
+    /// Title of the cheapest meals.
+    CheapestMeals,
+    /// No meal has a price for the price group.
+    NoPricedMeals,
+    /// A meme could not be fetched.
+    MemeFetchFailed,
+    /// Memes of a subreddit are not allowed in a channel.
+    SubredditNotAllowed,
+    /// A subreddit name is malformed.
+    InvalidSubreddit,
+    /// No fetched meme passed the content filters.
+    NoSuitableMeme,
+    /// Label of the author of a meme.
+    Author,
+    /// Label of the subreddit of a meme.
+    Subreddit,
+    /// Label of the upvotes of a meme.
+    Upvotes,
+    /// Title of the best-rated memes of the week.
+    TopMemes,
+    /// No memes were rated this week.
+    NoRatedMemes,
+    /// The fact of the day could not be fetched.
+    DailyFactFetchFailed,
+    /// A random fact could not be fetched.
+    RandomFactFetchFailed,
+    /// Facts are not available in a language.
+    UnsupportedLanguage,
+    /// Label of the languages facts are available in.
+    SupportedLanguages,
+    /// Title of the fact of the day.
+    FactOfTheDay,
+    /// Title of a random fact.
+    RandomFact,
+    /// Label of the source of a fact.
+    Source,
+    /// Button to show another fact.
+    AnotherOne,
+    /// Button to share a fact in the channel.
+    Share,
+    /// Introduction of a fact shared in the channel.
+    SharedFact,
+    /// A fact could not be shared in the channel.
+    ShareFailed,
+    /// Someone else pressed the buttons of a fact.
+    NotYourButtons,
+    /// Question of a multiple choice trivia round.
+    TriviaWhichIsTrue,
+    /// Question of a true or false trivia round.
+    TriviaTrueOrFalse,
+    /// Answer that a fact is true.
+    True,
+    /// Answer that a fact is false.
+    False,
+    /// Label of the time left to answer a trivia question.
+    TriviaTimeLimit,
+    /// A trivia answer was recorded.
+    TriviaAnswerRecorded,
+    /// A player already answered the trivia question.
+    TriviaAlreadyAnswered,
+    /// Nobody answered a trivia question correctly.
+    TriviaNoWinners,
+    /// Label of the players who answered correctly.
+    TriviaWinners,
+    /// Label of the correct trivia answer.
+    TriviaCorrectAnswer,
+    /// Title of the trivia leaderboard.
+    TriviaLeaderboard,
+    /// Nobody has trivia points yet.
+    TriviaNoScores,
+    /// Unit of trivia points.
+    TriviaPoints,
+    /// Title of the command overview.
+    Commands,
+    /// Hint how to see the details of a command.
+    HelpFooter,
+    /// The help was asked for a command that does not exist.
+    UnknownCommand,
+    /// A command has no description.
+    NoDescription,
+    /// Marker of optional parameters.
+    Optional,
+    /// Label of the parameters of a command.
+    Parameters,
+    /// Label of the subcommands of a command.
+    Subcommands,
+    /// Label of usage examples.
+    Examples,
+    /// Label of the cooldowns of a command.
+    Cooldown,
+    /// Scope of a cooldown shared by everyone.
+    CooldownGlobal,
+    /// Scope of a cooldown per user.
+    CooldownPerUser,
+    /// Scope of a cooldown per server.
+    CooldownPerGuild,
+    /// Scope of a cooldown per channel.
+    CooldownPerChannel,
+    /// Scope of a cooldown per server member.
+    CooldownPerMember,
+    /// Label of the restrictions of a command.
+    Restrictions,
+    /// Label of the permissions a command requires.
+    Permissions,
+    /// A command is restricted to the bot owners.
+    OwnersOnly,
+    /// A command only works on servers.
+    ServersOnly,
+    /// A command only works in direct messages.
+    DirectMessagesOnly,
+    /// A command only works in NSFW channels.
+    NsfwChannelsOnly,
+    /// The application commands were registered again.
+    CommandsRegistered,
+    /// Commands were registered for all servers.
+    Globally,
+    /// Unit of the servers commands were registered in.
+    Servers,
+}
+
+/// Translates a message into the given language.
+///
+/// # Arguments
+///
+/// * `language` - The language to translate into.
+/// * `message` - The message to translate.
+///
+/// # Returns
+///
+/// The translated message.
+pub fn tr(language: Language, message: Message) -> &'static str {
+    match language {
+        Language::En => english(message),
+        Language::De => german(message),
+    }
+}
+
+/// Returns the short name of a weekday.
+///
+/// # Arguments
+///
+/// * `language` - The language to translate into.
+/// * `day` - The weekday.
+///
+/// # Returns
+///
+/// The abbreviated weekday, e.g. `Mon` or `Mo`.
+pub fn weekday(language: Language, day: Weekday) -> &'static str {
+    const ENGLISH: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    const GERMAN: [&str; 7] = ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"];
+
+    let index = day.num_days_from_monday() as usize;
+    match language {
+        Language::En => ENGLISH[index],
+        Language::De => GERMAN[index],
+    }
+}
+
+/// Formats a date with its weekday, e.g. `Mon, 2024-07-01` or `Mo, 01.07.2024`.
+///
+/// # Arguments
+///
+/// * `language` - The language to format in.
+/// * `date` - The date.
+///
+/// # Returns
+///
+/// The formatted date.
+pub fn date(language: Language, date: NaiveDate) -> String {
+    let format = match language {
+        Language::En => "%Y-%m-%d",
+        Language::De => "%d.%m.%Y",
+    };
+    format!(
+        "{}, {}",
+        weekday(language, date.weekday()),
+        date.format(format)
+    )
+}
+
+/// The English message catalog.
+fn english(message: Message) -> &'static str {
+    match message {
+        Message::CanteenNotFound => "Canteen not found.",
+        Message::MealNotOnMenu => "This meal is not on today's menu of the canteen.",
+        Message::CanteenFetchFailed => "Failed to fetch canteen.",
+        Message::MealsFetchFailed => "Failed to fetch meals",
+        Message::NoMeals => "No meals found for the selected canteen.",
+        Message::Category => "Category",
+        Message::Prices => "Prices",
+        Message::Notes => "Notes",
+        Message::Students => "Students",
+        Message::Employees => "Employees",
+        Message::Pupils => "Pupils",
+        Message::Others => "Others",
+        Message::Name => "Name",
+        Message::Rating => "Rating",
+        Message::LatestReview => "Latest review",
+        Message::RatingSaved => "Thanks for rating",
+        Message::AlertAdded => {
+            "Alert added. You will get a direct message when a matching meal is on the menu."
+        }
+        Message::AlertRemoved => "Alert removed.",
+        Message::AlertNotFound => "You have no alert with this pattern.",
+        Message::AlertMatched => "Your dish alert matched:",
+        Message::NoAlerts => "You have no alerts.",
+        Message::TooManyAlerts => "You have reached the maximum number of alerts",
+        Message::InvalidPattern => "Invalid pattern",
+        Message::EmptyPattern => "Please enter a text to look for in meal names.",
+        Message::AlertNeedsCanteen => {
+            "No canteens are configured, please choose a canteen to watch."
+        }
+        Message::ConfiguredCanteens => "configured canteens",
+        Message::LunchAt => "Lunch at",
+        Message::Attendees => "Attendees",
+        Message::NoAttendees => "Nobody yet",
+        Message::Menu => "Today's menu",
+        Message::Join => "Join",
+        Message::Leave => "Leave",
+        Message::LunchReminder => "lunch starts in 10 minutes:",
+        Message::LunchClosed => "Sign-up closed",
+        Message::InvalidTime => "Invalid time, please use the HH:MM format.",
+        Message::TimeInPast => "This time has already passed today.",
+        Message::City => "City",
+        Message::Address => "Address",
+        Message::Coordinates => "Coordinates",
+        Message::Map => "Map",
+        Message::OpeningDays => "Coming week",
+        Message::Open => "open",
+        Message::Closed => "closed",
+        Message::Unknown => "unknown",
+        Message::NoCanteens => "No canteens found.",
+        Message::NoCities => "No cities configured in `locations.cities`.",
+        Message::NoCanteensConfigured => {
+            "No canteens found. Pass --id or --location, or add canteens with `config discover`."
+        }
+        Message::CanteensIn => "Canteens in",
+        Message::SelectCanteens => {
+            "Numbers of the canteens to add (e.g. \"1 3\", \"all\", empty for none):"
+        }
+        Message::InvalidSelection => "Invalid selection, please enter numbers from the list.",
+        Message::CanteensAdded => "New canteen IDs added to",
+        Message::ConfigCreated => "Configuration template written to",
+        Message::ConfigExists => {
+            "The configuration file already exists, pass --force to overwrite it:"
+        }
+        Message::ConfigValid => "The configuration is valid.",
+        Message::ConfigProblems => "Problems found in the configuration",
+        Message::CanteenNotOnOpenMensa => "Canteen does not exist on OpenMensa",
+        Message::CreateFailed => "Failed to create",
+        Message::WriteFailed => "Failed to write",
+        Message::ConfigReadFailed => "Error reading config file",
+        Message::ConfigParseFailed => "Failed to parse the TOML",
+        Message::CheapestFor => "Cheapest for",
+        Message::Comparison => "Comparison",
+        Message::TooFewCanteens => "Choose at least two different canteens.",
+        Message::Canteen => "Canteen",
+        Message::DishNotFound => "No matching dish found on the menus of the configured canteens.",
+        Message::EmptyQuery => "Please enter a dish to look for.",
+        Message::MatchesFor => "Matches for",
+        Message::NoGuildCanteens => "No canteens are configured for this server.",
+        Message::MoreResults => "more results",
+        Message::CheapestMeals => "Cheapest meals",
+        Message::NoPricedMeals => "No meals with a price for this group found.",
+        Message::MemeFetchFailed => "Failed to fetch meme.",
+        Message::SubredditNotAllowed => "Memes from this subreddit are not allowed here",
+        Message::InvalidSubreddit => "This is not a valid subreddit name",
+        Message::NoSuitableMeme => "No meme passed the content filters, please try again.",
+        Message::Author => "Author",
+        Message::Subreddit => "Subreddit",
+        Message::Upvotes => "Upvotes",
+        Message::TopMemes => "Top memes of the week",
+        Message::NoRatedMemes => "No memes have been rated this week.",
+        Message::DailyFactFetchFailed => "Failed to fetch daily fact.",
+        Message::RandomFactFetchFailed => "Failed to fetch random fact.",
+        Message::UnsupportedLanguage => "Unsupported language",
+        Message::SupportedLanguages => "Supported languages",
+        Message::FactOfTheDay => "Fact of the day",
+        Message::RandomFact => "Random fact",
+        Message::Source => "Source",
+        Message::AnotherOne => "Another one",
+        Message::Share => "Share",
+        Message::SharedFact => "shared a fact:",
+        Message::ShareFailed => "Failed to share the fact in this channel.",
+        Message::NotYourButtons => "Only the person who asked for this fact can use these buttons.",
+        Message::TriviaWhichIsTrue => "Which of these facts is true?",
+        Message::TriviaTrueOrFalse => "True or false?",
+        Message::True => "True",
+        Message::False => "False",
+        Message::TriviaTimeLimit => "Time to answer",
+        Message::TriviaAnswerRecorded => "Your answer has been recorded.",
+        Message::TriviaAlreadyAnswered => "You have already answered.",
+        Message::TriviaNoWinners => "Nobody got it right.",
+        Message::TriviaWinners => "Correct answers",
+        Message::TriviaCorrectAnswer => "Correct answer",
+        Message::TriviaLeaderboard => "Trivia leaderboard",
+        Message::TriviaNoScores => "Nobody has scored any points yet.",
+        Message::TriviaPoints => "points",
+        Message::Commands => "Commands",
+        Message::HelpFooter => "Use /help <command> to see parameters and examples.",
+        Message::UnknownCommand => "Unknown command",
+        Message::NoDescription => "No description available.",
+        Message::Optional => "optional",
+        Message::Parameters => "Parameters",
+        Message::Subcommands => "Subcommands",
+        Message::Examples => "Examples",
+        Message::Cooldown => "Cooldown",
+        Message::CooldownGlobal => "global",
+        Message::CooldownPerUser => "per user",
+        Message::CooldownPerGuild => "per server",
+        Message::CooldownPerChannel => "per channel",
+        Message::CooldownPerMember => "per member",
+        Message::Restrictions => "Restrictions",
+        Message::Permissions => "Permissions",
+        Message::OwnersOnly => "Bot owners only",
+        Message::ServersOnly => "Servers only",
+        Message::DirectMessagesOnly => "Direct messages only",
+        Message::NsfwChannelsOnly => "NSFW channels only",
+        Message::CommandsRegistered => "Commands registered",
+        Message::Globally => "globally",
+        Message::Servers => "servers",
+    }
+}
+
+/// The German message catalog.
+fn german(message: Message) -> &'static str {
+    match message {
+        Message::CanteenNotFound => "Mensa nicht gefunden.",
+        Message::MealNotOnMenu => "Dieses Gericht steht heute nicht auf dem Speiseplan der Mensa.",
+        Message::CanteenFetchFailed => "Mensa konnte nicht abgerufen werden.",
+        Message::MealsFetchFailed => "Gerichte konnten nicht abgerufen werden",
+        Message::NoMeals => "Keine Gerichte für die gewählte Mensa gefunden.",
+        Message::Category => "Kategorie",
+        Message::Prices => "Preise",
+        Message::Notes => "Hinweise",
+        Message::Students => "Studierende",
+        Message::Employees => "Bedienstete",
+        Message::Pupils => "Schüler",
+        Message::Others => "Gäste",
+        Message::Name => "Name",
+        Message::Rating => "Bewertung",
+        Message::LatestReview => "Neueste Rezension",
+        Message::RatingSaved => "Danke für deine Bewertung von",
+        Message::AlertAdded => "Alarm angelegt. Du bekommst eine DM, sobald ein passendes Gericht auf dem Speiseplan steht.",
+        Message::AlertRemoved => "Alarm entfernt.",
+        Message::AlertNotFound => "Du hast keinen Alarm mit diesem Muster.",
+        Message::AlertMatched => "Dein Gericht-Alarm hat angeschlagen:",
+        Message::NoAlerts => "Du hast keine Alarme.",
+        Message::TooManyAlerts => "Du hast die maximale Anzahl an Alarmen erreicht",
+        Message::InvalidPattern => "Ungültiges Muster",
+        Message::EmptyPattern => "Bitte gib einen Text ein, nach dem in Gerichten gesucht werden soll.",
+        Message::AlertNeedsCanteen => "Es sind keine Mensen konfiguriert, bitte wähle eine Mensa aus.",
+        Message::ConfiguredCanteens => "konfigurierte Mensen",
+        Message::LunchAt => "Mittagessen in der",
+        Message::Attendees => "Teilnehmende",
+        Message::NoAttendees => "Noch niemand",
+        Message::Menu => "Heutiger Speiseplan",
+        Message::Join => "Dabei",
+        Message::Leave => "Doch nicht",
+        Message::LunchReminder => "das Mittagessen beginnt in 10 Minuten:",
+        Message::LunchClosed => "Anmeldung geschlossen",
+        Message::InvalidTime => "Ungültige Uhrzeit, bitte nutze das Format HH:MM.",
+        Message::TimeInPast => "Diese Uhrzeit ist heute schon vorbei.",
+        Message::City => "Stadt",
+        Message::Address => "Adresse",
+        Message::Coordinates => "Koordinaten",
+        Message::Map => "Karte",
+        Message::OpeningDays => "Kommende Woche",
+        Message::Open => "geöffnet",
+        Message::Closed => "geschlossen",
+        Message::Unknown => "unbekannt",
+        Message::NoCanteens => "Keine Mensen gefunden.",
+        Message::NoCities => "Keine Städte in `locations.cities` konfiguriert.",
+        Message::NoCanteensConfigured => "Keine Mensen gefunden. Nutze --id oder --location oder füge Mensen mit `config discover` hinzu.",
+        Message::CanteensIn => "Mensen in",
+        Message::SelectCanteens => "Nummern der hinzuzufügenden Mensen (z. B. \"1 3\", \"all\", leer für keine):",
+        Message::InvalidSelection => "Ungültige Auswahl, bitte gib Nummern aus der Liste ein.",
+        Message::CanteensAdded => "Neue Mensa-IDs hinzugefügt zu",
+        Message::ConfigCreated => "Konfigurationsvorlage geschrieben nach",
+        Message::ConfigExists => "Die Konfigurationsdatei existiert bereits, nutze --force zum Überschreiben:",
+        Message::ConfigValid => "Die Konfiguration ist gültig.",
+        Message::ConfigProblems => "Probleme in der Konfiguration gefunden",
+        Message::CanteenNotOnOpenMensa => "Diese Mensa gibt es auf OpenMensa nicht",
+        Message::CreateFailed => "Fehler beim Erstellen von",
+        Message::WriteFailed => "Fehler beim Schreiben von",
+        Message::ConfigReadFailed => "Fehler beim Lesen der Konfigurationsdatei",
+        Message::ConfigParseFailed => "Fehler beim Parsen des TOML",
+        Message::CheapestFor => "Am günstigsten für",
+        Message::Comparison => "Vergleich",
+        Message::TooFewCanteens => "Wähle mindestens zwei verschiedene Mensen.",
+        Message::Canteen => "Mensa",
+        Message::DishNotFound => "Kein passendes Gericht auf den Speiseplänen der konfigurierten Mensen gefunden.",
+        Message::EmptyQuery => "Bitte gib ein Gericht ein, nach dem gesucht werden soll.",
+        Message::MatchesFor => "Treffer für",
+        Message::NoGuildCanteens => "Für diesen Server sind keine Mensen konfiguriert.",
+        Message::MoreResults => "weitere Treffer",
+        Message::CheapestMeals => "Günstigste Gerichte",
+        Message::NoPricedMeals => "Keine Gerichte mit einem Preis für diese Gruppe gefunden.",
+        Message::MemeFetchFailed => "Meme konnte nicht abgerufen werden.",
+        Message::SubredditNotAllowed => "Memes aus diesem Subreddit sind hier nicht erlaubt",
+        Message::InvalidSubreddit => "Das ist kein gültiger Subreddit-Name",
+        Message::NoSuitableMeme => {
+            "Kein Meme hat die Inhaltsfilter bestanden, bitte versuche es erneut."
+        }
+        Message::Author => "Autor",
+        Message::Subreddit => "Subreddit",
+        Message::Upvotes => "Upvotes",
+        Message::TopMemes => "Die besten Memes der Woche",
+        Message::NoRatedMemes => "Diese Woche wurden noch keine Memes bewertet.",
+        Message::DailyFactFetchFailed => "Fakt des Tages konnte nicht abgerufen werden.",
+        Message::RandomFactFetchFailed => "Zufälliger Fakt konnte nicht abgerufen werden.",
+        Message::UnsupportedLanguage => "Nicht unterstützte Sprache",
+        Message::SupportedLanguages => "Unterstützte Sprachen",
+        Message::FactOfTheDay => "Fakt des Tages",
+        Message::RandomFact => "Zufälliger Fakt",
+        Message::Source => "Quelle",
+        Message::AnotherOne => "Noch einer",
+        Message::Share => "Teilen",
+        Message::SharedFact => "hat einen Fakt geteilt:",
+        Message::ShareFailed => "Der Fakt konnte in diesem Kanal nicht geteilt werden.",
+        Message::NotYourButtons => "Nur die Person, die den Fakt angefordert hat, kann diese Knöpfe benutzen.",
+        Message::TriviaWhichIsTrue => "Welcher dieser Fakten ist wahr?",
+        Message::TriviaTrueOrFalse => "Wahr oder falsch?",
+        Message::True => "Wahr",
+        Message::False => "Falsch",
+        Message::TriviaTimeLimit => "Zeit zum Antworten",
+        Message::TriviaAnswerRecorded => "Deine Antwort wurde gespeichert.",
+        Message::TriviaAlreadyAnswered => "Du hast bereits geantwortet.",
+        Message::TriviaNoWinners => "Niemand lag richtig.",
+        Message::TriviaWinners => "Richtige Antworten",
+        Message::TriviaCorrectAnswer => "Richtige Antwort",
+        Message::TriviaLeaderboard => "Trivia-Rangliste",
+        Message::TriviaNoScores => "Bisher hat niemand Punkte erzielt.",
+        Message::TriviaPoints => "Punkte",
+        Message::Commands => "Befehle",
+        Message::HelpFooter => "Nutze /hilfe <befehl>, um Parameter und Beispiele zu sehen.",
+        Message::UnknownCommand => "Unbekannter Befehl",
+        Message::NoDescription => "Keine Beschreibung verfügbar.",
+        Message::Optional => "optional",
+        Message::Parameters => "Parameter",
+        Message::Subcommands => "Unterbefehle",
+        Message::Examples => "Beispiele",
+        Message::Cooldown => "Abklingzeit",
+        Message::CooldownGlobal => "global",
+        Message::CooldownPerUser => "pro Person",
+        Message::CooldownPerGuild => "pro Server",
+        Message::CooldownPerChannel => "pro Kanal",
+        Message::CooldownPerMember => "pro Mitglied",
+        Message::Restrictions => "Einschränkungen",
+        Message::Permissions => "Berechtigungen",
+        Message::OwnersOnly => "Nur für Bot-Besitzer",
+        Message::ServersOnly => "Nur auf Servern",
+        Message::DirectMessagesOnly => "Nur in Direktnachrichten",
+        Message::NsfwChannelsOnly => "Nur in NSFW-Kanälen",
+        Message::CommandsRegistered => "Befehle registriert",
+        Message::Globally => "global",
+        Message::Servers => "Server",
+    }
+}
@@ -1,17 +1,21 @@
-use chrono::{NaiveDate, Utc};
+use chrono::NaiveDate;
+use chrono_tz::Tz;
 use clap::Parser;
-use dotenv::dotenv;
-use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 mod apis;
 mod args;
 mod bot;
 mod cli;
+mod clock;
 mod config;
+mod i18n;
 mod models;
+mod ratings;
+mod secret;
+mod storage;
 
-use args::Args;
+use args::{Args, CanteensCommand, Command, ConfigCommand, TokenCommand};
 use config::Configs;
 
 extern crate openmensa_rust_interface;
@@ -24,13 +28,14 @@ use openmensa_rust_interface::{
 /// # Arguments
 ///
 /// * `date_str` - A string slice that holds the date in format "YYYY-MM-DD" or "today".
+/// * `timezone` - The timezone that decides what "today" is.
 ///
 /// # Returns
 ///
 /// Returns `Ok(NaiveDate)` if parsing is successful, otherwise returns `Err(String)`.
-fn parse_date(date_str: &str) -> Result<NaiveDate, String> {
+fn parse_date(date_str: &str, timezone: Tz) -> Result<NaiveDate, String> {
     match date_str {
-        "today" => Ok(Utc::now().date_naive()), // Using naive_local() for compatibility
+        "today" => Ok(clock::today(timezone)),
         _ => NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
             .map_err(|err| format!("Invalid date format: {}", err)),
     }
@@ -71,6 +76,21 @@ async fn fetch_canteens(args: &Args, configs: &Configs) -> Option<Vec<Canteen>>
         };
     }
 
+    if configs.locations.canteens.is_empty() {
+        // Without configured IDs, all canteens of the configured cities are shown
+        let mut canteens = Vec::new();
+        for city in &configs.locations.cities {
+            match get_canteens_by_location(city).await {
+                Ok(found) => canteens.extend(found),
+                Err(err) => {
+                    eprintln!("Error fetching canteens by location: {}", err);
+                    return None;
+                }
+            }
+        }
+        return Some(canteens);
+    }
+
     match get_canteens_by_ids(configs.locations.canteens.to_vec()).await {
         Ok(canteens) => Some(canteens),
         Err(err) => {
@@ -80,45 +100,138 @@ async fn fetch_canteens(args: &Args, configs: &Configs) -> Option<Vec<Canteen>>
     }
 }
 
-/// Reads and returns the bot token based on the provided arguments.
+/// Fetches canteens for subcommands that cannot run without any.
 ///
 /// # Arguments
 ///
 /// * `args` - A reference to `Args` struct containing command-line arguments.
+/// * `configs` - A reference to `Configs` struct containing configuration settings.
 ///
 /// # Returns
 ///
-/// Returns `Ok(String)` if a token is found, otherwise returns `Err(String)`.
-fn get_bot_token(args: &Args) -> Result<String, String> {
-    if args.token.is_none() && args.env_file.is_none() {
-        let path = Path::new(".env");
-        if !path.exists() {
-            return Err(
-                "Please provide a Discord Token either as a parameter or in a .env file".into(),
-            );
-        }
-
-        dotenv().ok();
-        return std::env::var("DISCORD_TOKEN")
-            .map_err(|_| "Could not find \"DISCORD_TOKEN\" in .env file".into());
+/// Returns the canteens, or a localized error if fetching failed or none are configured.
+async fn required_canteens(args: &Args, configs: &Configs) -> Result<Vec<Canteen>, String> {
+    let canteens = fetch_canteens(args, configs)
+        .await
+        .ok_or_else(|| i18n::tr(configs.language, i18n::Message::CanteenFetchFailed).to_string())?;
+    if canteens.is_empty() {
+        return Err(i18n::tr(configs.language, i18n::Message::NoCanteensConfigured).to_string());
     }
+    Ok(canteens)
+}
 
-    if let Some(ref env_path) = args.env_file {
-        let path = Path::new(env_path);
-        if !path.exists() {
-            return Err("Wrong path passed to arg".into());
+/// Runs a CLI subcommand.
+///
+/// # Arguments
+///
+/// * `command` - The subcommand to run.
+/// * `args` - A reference to `Args` struct containing command-line arguments.
+/// * `config_path` - The path of the configuration file.
+/// * `load_configs` - Loads the configuration for the subcommands that need it.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command succeeded, otherwise returns `Err(String)`.
+async fn handle_command(
+    command: &Command,
+    args: &Args,
+    config_path: &Path,
+    load_configs: impl Fn() -> Result<Configs, String>,
+) -> Result<(), String> {
+    match command {
+        Command::Canteens { command } => {
+            let configs = load_configs()?;
+            match command {
+                CanteensCommand::Show { canteen } => {
+                    cli::canteens::show(
+                        canteen,
+                        clock::today(configs.timezone_for(None)),
+                        configs.language,
+                    )
+                    .await
+                }
+                CanteensCommand::List { json } => {
+                    cli::canteens::list(*json, configs.language).await
+                }
+                CanteensCommand::Search {
+                    name,
+                    city,
+                    lat,
+                    lng,
+                    radius,
+                    json,
+                } => {
+                    let search = cli::canteens::Search {
+                        name: name.as_deref(),
+                        city: city.as_deref(),
+                        near: lat.zip(*lng),
+                        radius: *radius,
+                    };
+                    cli::canteens::search(search, *json, configs.language).await
+                }
+            }
         }
-
-        dotenv().ok();
-        return std::env::var("DISCORD_TOKEN")
-            .map_err(|_| "Could not find \"DISCORD_TOKEN\" in .env file".into());
-    }
-
-    if let Some(ref arg_token) = args.token {
-        return Ok(arg_token.clone());
+        Command::Config { command } => {
+            // A broken config must not stop the commands that create or check it
+            let language = load_configs()
+                .map(|configs| configs.language)
+                .unwrap_or_default();
+            match command {
+                ConfigCommand::Discover { all } => {
+                    cli::config::discover(&load_configs()?, config_path, *all).await
+                }
+                ConfigCommand::Init { force } => cli::config::init(config_path, *force, language),
+                ConfigCommand::Check => cli::config::check(config_path, language).await,
+            }
+        }
+        Command::Compare {
+            canteens,
+            date,
+            price_group,
+        } => {
+            let configs = load_configs()?;
+            let date = parse_date(date, configs.timezone_for(None))?;
+            cli::compare::compare(canteens, date, *price_group, configs.language).await
+        }
+        Command::Search { dish, date } => {
+            let configs = load_configs()?;
+            if dish.trim().is_empty() {
+                return Err(i18n::tr(configs.language, i18n::Message::EmptyQuery).to_string());
+            }
+            let date = parse_date(date, configs.timezone_for(None))?;
+            let canteens = required_canteens(args, &configs).await?;
+            cli::find::find(&canteens, dish, date, configs.language).await
+        }
+        Command::Cheapest {
+            date,
+            price_group,
+            count,
+            full_meals_only,
+        } => {
+            let configs = load_configs()?;
+            let date = parse_date(date, configs.timezone_for(None))?;
+            let canteens = required_canteens(args, &configs).await?;
+            cli::cheapest::cheapest(
+                &canteens,
+                date,
+                *price_group,
+                *count as usize,
+                *full_meals_only,
+                configs.language,
+            )
+            .await
+        }
+        Command::Token { command } => match command {
+            TokenCommand::Check => {
+                // The token may come from elsewhere, so a broken config only loses `bot.token`
+                let configs = load_configs().unwrap_or_else(|err| {
+                    eprintln!("{}", err);
+                    Configs::default()
+                });
+                cli::token::check(args.token.as_ref(), args.env_file.as_deref(), &configs)
+            }
+        },
     }
-
-    Err("No valid token source provided".into())
 }
 
 /// Handles Discord bot functionality.
@@ -126,62 +239,113 @@ fn get_bot_token(args: &Args) -> Result<String, String> {
 /// # Arguments
 ///
 /// * `args` - A reference to `Args` struct containing command-line arguments.
+/// * `configs` - The `Configs` struct containing configuration settings, handed to the bot.
+/// * `config_path` - The path of the configuration file, reloaded by the bot when it changes.
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` if the bot starts successfully, otherwise returns `Err(String)`.
-async fn handle_discord_bot(args: &Args) -> Result<(), String> {
-    let token = get_bot_token(args)?;
-    bot::start_bot(&token).await;
-    Ok(())
+/// Returns `Ok(())` once the bot was shut down cleanly, otherwise returns `Err(String)`.
+async fn handle_discord_bot(
+    args: &Args,
+    configs: Configs,
+    config_path: PathBuf,
+) -> Result<(), String> {
+    let (token, source) = secret::resolve_token(
+        args.token.as_ref(),
+        args.env_file.as_deref(),
+        configs.bot.token.as_ref(),
+        |name| std::env::var(name).ok(),
+    )?;
+    println!("Using the Discord token from {}", source);
+    let token = token.expose();
+
+    // Guilds passed on the command line take precedence over the configured ones
+    let guilds = if args.guilds.is_empty() {
+        &configs.bot.guilds
+    } else {
+        &args.guilds
+    };
+    let registration = bot::Registration::from_guild_ids(guilds);
+
+    if args.unregister {
+        return bot::unregister_commands(token, &registration)
+            .await
+            .map_err(|err| format!("Failed to unregister commands: {}", err));
+    }
+
+    bot::start_bot(token, registration, configs, config_path)
+        .await
+        .map_err(|err| err.to_string())
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
 
-    // Read configuration file
-    let config_path = "~/.config/discord-bot/config.toml";
-    let expanded_path = shellexpand::tilde(config_path).into_owned();
+    // The configuration is only loaded by the modes that need it
+    let config_path = config::resolve_path(args.config.as_deref());
+    let load_configs = || config::load(&config_path, args.config.is_some());
 
-    // Handle Discord bot functionality
-    if args.discord_bot {
-        if let Err(err) = handle_discord_bot(&args).await {
+    if let Some(command) = &args.command {
+        if let Err(err) = handle_command(command, &args, &config_path, load_configs).await {
             eprintln!("{}", err);
-            return;
+            std::process::exit(1);
         }
+        return;
     }
 
-    let configs_file = match fs::read_to_string(Path::new(&expanded_path)) {
-        Ok(contents) => contents,
-        Err(err) => {
-            eprintln!("Error reading config file: {}", err);
-            return;
+    // Handle Discord bot functionality
+    if args.discord_bot || args.unregister {
+        let configs = match load_configs() {
+            Ok(configs) => configs,
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+        };
+
+        if let Err(err) = handle_discord_bot(&args, configs, config_path.clone()).await {
+            eprintln!("{}", err);
+            std::process::exit(1);
         }
-    };
+        return;
+    }
+
+    // Memes only use the configured language, so a broken config does not stop them
+    if args.meme {
+        let language = load_configs()
+            .map(|configs| configs.language)
+            .unwrap_or_else(|err| {
+                eprintln!("{}", err);
+                i18n::Language::default()
+            });
+        cli::meme(args.count, language).await;
+        return;
+    }
 
-    // Parse the TOML content
-    let configs: Configs = match toml::from_str(&configs_file) {
-        Ok(parsed) => parsed,
+    let configs = match load_configs() {
+        Ok(configs) => configs,
         Err(err) => {
-            eprintln!("Failed to parse the TOML: {}", err);
+            eprintln!("{}", err);
             return;
         }
     };
 
     // Handle CLI commands or print meals for canteens
-    if args.meme {
-        cli::meme().await;
-        return;
-    }
-
-    if args.daily_fact {
-        cli::daily_fact().await;
-        return;
-    }
+    if args.daily_fact || args.random_fact {
+        let fact_language = match configs.fact_language(args.lang.as_deref(), configs.language) {
+            Ok(code) => code,
+            Err(err) => {
+                eprintln!("{}", err);
+                return;
+            }
+        };
 
-    if args.random_fact {
-        cli::random_fact().await;
+        if args.daily_fact {
+            cli::daily_fact(fact_language, configs.language).await;
+        } else {
+            cli::random_fact(fact_language, configs.language).await;
+        }
         return;
     }
 
@@ -192,7 +356,15 @@ async fn main() {
 
     // Fetch and print meals for canteens
     if let Some(canteens) = fetch_canteens(&args, &configs).await {
-        let date = match parse_date(&args.date) {
+        if canteens.is_empty() {
+            eprintln!(
+                "{}",
+                i18n::tr(configs.language, i18n::Message::NoCanteensConfigured)
+            );
+            return;
+        }
+
+        let date = match parse_date(&args.date, configs.timezone_for(None)) {
             Ok(date) => date,
             Err(err) => {
                 eprintln!("Error parsing date: {}", err);
@@ -200,7 +372,7 @@ async fn main() {
             }
         };
 
-        if let Err(err) = cli::print_meals(canteens, date).await {
+        if let Err(err) = cli::print_meals(canteens, date, configs.language).await {
             eprintln!("Error printing meals: {}", err);
         }
     }
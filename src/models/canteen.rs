@@ -0,0 +1,184 @@
+use crate::apis::openmensa;
+use crate::i18n::{tr, weekday, Language, Message};
+use chrono::{Datelike, NaiveDate, TimeDelta};
+use openmensa_rust_interface::{get_canteen_by_id, get_canteen_by_name, Canteen};
+use serde::Serialize;
+use tabled::Tabled;
+
+/// Number of days shown in the opening calendar, starting today.
+const WEEK_DAYS: i64 = 7;
+
+/// Mean radius of the earth in kilometres.
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Represents a canteen formatted for tabular or JSON display.
+#[derive(Tabled, Serialize)]
+pub struct TabledCanteen {
+    /// The OpenMensa ID of the canteen.
+    pub id: u32,
+    /// The name of the canteen.
+    pub name: String,
+    /// The city of the canteen.
+    pub city: String,
+    /// The address of the canteen.
+    pub address: String,
+}
+
+/// Converts a Canteen into a TabledCanteen for tabular representation.
+impl From<Canteen> for TabledCanteen {
+    fn from(canteen: Canteen) -> Self {
+        TabledCanteen {
+            id: canteen.id,
+            name: canteen.name,
+            city: canteen.city,
+            address: canteen.address,
+        }
+    }
+}
+
+/// Calculates the distance between a canteen and a point.
+///
+/// # Arguments
+///
+/// * `canteen` - The canteen.
+/// * `latitude` - The latitude of the point.
+/// * `longitude` - The longitude of the point.
+///
+/// # Returns
+///
+/// The great-circle distance in kilometres, or `None` if the canteen has no coordinates.
+pub fn distance_km(canteen: &Canteen, latitude: f64, longitude: f64) -> Option<f64> {
+    let [canteen_latitude, canteen_longitude, ..] = canteen.coordinates.as_deref()? else {
+        return None;
+    };
+
+    let (lat1, lat2) = (latitude.to_radians(), canteen_latitude.to_radians());
+    let delta_lat = lat2 - lat1;
+    let delta_lng = (canteen_longitude - longitude).to_radians();
+
+    let a =
+        (delta_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (delta_lng / 2.0).sin().powi(2);
+    Some(2.0 * EARTH_RADIUS_KM * a.sqrt().asin())
+}
+
+/// Looks up a canteen by its ID or its exact name.
+///
+/// # Arguments
+///
+/// * `query` - The ID or the name of the canteen.
+///
+/// # Returns
+///
+/// The canteen, `Ok(None)` if there is none, or the error message if the lookup failed.
+pub async fn find(query: &str) -> Result<Option<Canteen>, String> {
+    let result = match query.trim().parse::<u32>() {
+        Ok(id) => get_canteen_by_id(id).await,
+        Err(_) => get_canteen_by_name(query.trim()).await,
+    };
+    result.map_err(|err| err.to_string())
+}
+
+/// Details of a canteen together with its opening days in the coming week.
+pub struct CanteenInfo {
+    /// The canteen.
+    pub canteen: Canteen,
+    /// Each day of the coming week and whether the canteen is open, `None` if unknown.
+    pub week: Vec<(NaiveDate, Option<bool>)>,
+}
+
+impl CanteenInfo {
+    /// Fetches the opening days of a canteen for the coming week.
+    ///
+    /// # Arguments
+    ///
+    /// * `canteen` - The canteen to describe.
+    /// * `today` - The first day of the week, today in the canteen's timezone.
+    ///
+    /// # Returns
+    ///
+    /// The canteen info, with unknown opening days if the calendar could not be fetched.
+    pub async fn fetch(canteen: Canteen, today: NaiveDate) -> Self {
+        let days = openmensa::days(canteen.id, today)
+            .await
+            .unwrap_or_else(|err| {
+                eprintln!("Error fetching days of canteen {}: {}", canteen.id, err);
+                Vec::new()
+            });
+
+        let week = (0..WEEK_DAYS)
+            .map(|offset| {
+                let date = today + TimeDelta::days(offset);
+                let open = days
+                    .iter()
+                    .find(|day| day.date == date.to_string())
+                    .map(|day| !day.closed);
+                (date, open)
+            })
+            .collect();
+
+        CanteenInfo { canteen, week }
+    }
+
+    /// Builds an OpenStreetMap link to the canteen.
+    ///
+    /// # Returns
+    ///
+    /// The link, or `None` if the canteen has no coordinates.
+    pub fn map_link(&self) -> Option<String> {
+        match self.canteen.coordinates.as_deref() {
+            Some([latitude, longitude, ..]) => Some(format!(
+                "https://www.openstreetmap.org/?mlat={0}&mlon={1}#map=17/{0}/{1}",
+                latitude, longitude
+            )),
+            _ => None,
+        }
+    }
+
+    /// Lists the details of the canteen as named values.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The language of the names and values.
+    ///
+    /// # Returns
+    ///
+    /// Pairs of a localized name and the formatted value.
+    pub fn fields(&self, language: Language) -> Vec<(&'static str, String)> {
+        let coordinates = match self.canteen.coordinates.as_deref() {
+            Some([latitude, longitude, ..]) => format!("{}, {}", latitude, longitude),
+            _ => String::from("-"),
+        };
+
+        let week = self
+            .week
+            .iter()
+            .map(|(date, open)| {
+                let state = match open {
+                    Some(true) => Message::Open,
+                    Some(false) => Message::Closed,
+                    None => Message::Unknown,
+                };
+                format!(
+                    "{} {}: {}",
+                    weekday(language, date.weekday()),
+                    date.format("%d.%m."),
+                    tr(language, state)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        vec![
+            ("ID", self.canteen.id.to_string()),
+            (tr(language, Message::Name), self.canteen.name.clone()),
+            (tr(language, Message::City), self.canteen.city.clone()),
+            (tr(language, Message::Address), self.canteen.address.clone()),
+            (tr(language, Message::Coordinates), coordinates),
+            (
+                tr(language, Message::Map),
+                self.map_link().unwrap_or_else(|| String::from("-")),
+            ),
+            (tr(language, Message::OpeningDays), week),
+        ]
+    }
+}
@@ -0,0 +1,186 @@
+use super::price::PriceGroup;
+use chrono::NaiveDate;
+use futures::future;
+use openmensa_rust_interface::{get_meals, Canteen, Meal};
+
+/// Menus of several canteens on the same day, grouped by meal category.
+pub struct Comparison {
+    /// Names of the compared canteens, in the order they were given.
+    pub canteens: Vec<String>,
+    /// One row per category, in the order the categories first appear.
+    pub rows: Vec<CategoryRow>,
+    /// The price group the cheapest meals were chosen for.
+    pub price_group: PriceGroup,
+}
+
+/// The meals of one category in each compared canteen.
+pub struct CategoryRow {
+    /// The name of the category.
+    pub category: String,
+    /// The meals of the category, one list per canteen.
+    pub meals: Vec<Vec<Meal>>,
+    /// The canteen and meal index of the cheapest meal, `None` if no meal has a positive price.
+    pub cheapest: Option<(usize, usize)>,
+}
+
+impl CategoryRow {
+    /// Checks whether a meal is the cheapest of its category.
+    ///
+    /// # Arguments
+    ///
+    /// * `canteen` - The index of the canteen.
+    /// * `meal` - The index of the meal within the canteen.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the meal is the highlighted one.
+    pub fn is_cheapest(&self, canteen: usize, meal: usize) -> bool {
+        self.cheapest == Some((canteen, meal))
+    }
+}
+
+impl Comparison {
+    /// Fetches the menus of all canteens concurrently and groups them by category.
+    ///
+    /// Canteens whose menu can not be fetched are logged and compared with an
+    /// empty menu, so one closed canteen does not spoil the whole comparison.
+    ///
+    /// # Arguments
+    ///
+    /// * `canteens` - The canteens to compare.
+    /// * `date` - The date of the menus.
+    /// * `price_group` - The price group used to find the cheapest meals.
+    ///
+    /// # Returns
+    ///
+    /// The comparison, or an error message naming the canteens if no menu could be fetched.
+    pub async fn fetch(
+        canteens: &[Canteen],
+        date: NaiveDate,
+        price_group: PriceGroup,
+    ) -> Result<Self, String> {
+        let date = date.to_string();
+        let menus =
+            future::join_all(canteens.iter().map(|canteen| get_meals(canteen, &date))).await;
+
+        let mut meals = Vec::new();
+        let mut errors = Vec::new();
+        for (canteen, menu) in canteens.iter().zip(menus) {
+            match menu {
+                Ok(menu) => meals.push(menu),
+                Err(err) => {
+                    eprintln!("Error fetching meals of {}: {}", canteen.name, err);
+                    errors.push(format!("{}: {}", canteen.name, err));
+                    meals.push(Vec::new());
+                }
+            }
+        }
+        if !canteens.is_empty() && errors.len() == canteens.len() {
+            return Err(errors.join("\n"));
+        }
+
+        Ok(Comparison {
+            canteens: canteens
+                .iter()
+                .map(|canteen| canteen.name.clone())
+                .collect(),
+            rows: group_by_category(meals, price_group),
+            price_group,
+        })
+    }
+}
+
+/// Groups the menus of several canteens by category.
+///
+/// # Arguments
+///
+/// * `menus` - The meals of each canteen.
+/// * `price_group` - The price group used to find the cheapest meals.
+///
+/// # Returns
+///
+/// One row per category with the cheapest meal marked.
+fn group_by_category(menus: Vec<Vec<Meal>>, price_group: PriceGroup) -> Vec<CategoryRow> {
+    let count = menus.len();
+    let mut rows: Vec<CategoryRow> = Vec::new();
+
+    for (index, menu) in menus.into_iter().enumerate() {
+        for meal in menu {
+            let position = rows.iter().position(|row| row.category == meal.category);
+            let row = match position {
+                Some(position) => &mut rows[position],
+                None => {
+                    rows.push(CategoryRow {
+                        category: meal.category.clone(),
+                        meals: vec![Vec::new(); count],
+                        cheapest: None,
+                    });
+                    rows.last_mut().unwrap()
+                }
+            };
+            row.meals[index].push(meal);
+        }
+    }
+
+    for row in &mut rows {
+        let mut cheapest: Option<((usize, usize), f64)> = None;
+        for (canteen, meals) in row.meals.iter().enumerate() {
+            for (index, meal) in meals.iter().enumerate() {
+                // Zero prices mark free items and placeholders, not real offers
+                let Some(price) = price_group.price(&meal.prices).filter(|price| *price > 0.0)
+                else {
+                    continue;
+                };
+                if cheapest.is_none_or(|(_, lowest)| price < lowest) {
+                    cheapest = Some(((canteen, index), price));
+                }
+            }
+        }
+        row.cheapest = cheapest.map(|(position, _)| position);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meal(category: &str, students: Option<f64>) -> Meal {
+        serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "name": "Meal",
+            "category": category,
+            "prices": {
+                "students": students,
+                "employees": null,
+                "pupils": null,
+                "others": null
+            },
+            "notes": []
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn zero_prices_are_never_the_cheapest() {
+        let menus = vec![
+            vec![meal("Hauptgericht", Some(0.0))],
+            vec![meal("Hauptgericht", Some(3.5)), meal("Hauptgericht", None)],
+        ];
+
+        let rows = group_by_category(menus, PriceGroup::Students);
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].cheapest, Some((1, 0)));
+    }
+
+    #[test]
+    fn rows_keep_empty_columns_for_canteens_without_meals() {
+        let menus = vec![vec![meal("Suppen", Some(1.2))], Vec::new()];
+
+        let rows = group_by_category(menus, PriceGroup::Students);
+
+        assert_eq!(rows[0].meals[0].len(), 1);
+        assert!(rows[0].meals[1].is_empty());
+    }
+}
@@ -1,18 +1,23 @@
 use serde::Deserialize;
 
 /// Represents a location with cities, coordinates, and associated canteens.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct Location {
     /// List of cities associated with this location.
+    #[serde(default)]
     pub cities: Vec<String>,
     /// Optional coordinates (latitude and longitude) associated with this location.
+    #[serde(default)]
     pub coordinates: Option<Vec<Coordinate>>,
     /// List of canteen IDs associated with this location.
+    #[serde(default)]
     pub canteens: Vec<u32>,
 }
 
 /// Represents geographical coordinates (latitude and longitude) of a city.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct Coordinate {
     /// The name of the city.
     pub city: String,
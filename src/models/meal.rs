@@ -14,6 +14,8 @@ pub struct TabledMeal {
     pub guest_price: f64,
     /// Additional notes or information about the meal.
     pub notes: String,
+    /// The average user rating of the meal.
+    pub rating: String,
 }
 
 /// Converts a Meal into a TabledMeal for tabular representation.
@@ -34,6 +36,7 @@ impl From<Meal> for TabledMeal {
             employee_price: meal.prices.employees.unwrap_or(0.0),
             guest_price: meal.prices.pupils.unwrap_or(0.0),
             notes: meal.notes.join(", "),
+            rating: String::from("-"),
         }
     }
 }
@@ -0,0 +1,260 @@
+use super::price::PriceGroup;
+use chrono::NaiveDate;
+use futures::future;
+use openmensa_rust_interface::{get_meals, Canteen, Meal};
+
+/// Words in category names that mark sides, desserts and drinks rather than full meals.
+///
+/// The words are compared against the whole words of normalized category names, so
+/// categories like "Suppen" or "Salate" that also hold main dishes are kept.
+const SIDE_CATEGORIES: &[&str] = &[
+    "beilage",
+    "beilagen",
+    "beilagensalat",
+    "salatbar",
+    "dessert",
+    "desserts",
+    "nachspeise",
+    "nachspeisen",
+    "nachtisch",
+    "getrank",
+    "getranke",
+    "side",
+    "sides",
+    "drink",
+    "drinks",
+];
+
+/// A meal together with the canteen serving it.
+pub struct ServedMeal {
+    /// The name of the canteen.
+    pub canteen: String,
+    /// The meal.
+    pub meal: Meal,
+}
+
+/// Fetches the menus of several canteens concurrently.
+///
+/// Canteens whose menu can not be fetched are logged and left out, so one
+/// unavailable canteen does not spoil the results of the others.
+///
+/// # Arguments
+///
+/// * `canteens` - The canteens to fetch the menus of.
+/// * `date` - The date of the menus.
+///
+/// # Returns
+///
+/// The meals of all canteens, in the order of the canteens.
+pub async fn fetch_all(canteens: &[Canteen], date: NaiveDate) -> Vec<ServedMeal> {
+    let date = date.to_string();
+    let menus = future::join_all(canteens.iter().map(|canteen| get_meals(canteen, &date))).await;
+
+    canteens
+        .iter()
+        .zip(menus)
+        .flat_map(|(canteen, menu)| {
+            let meals = menu.unwrap_or_else(|err| {
+                eprintln!("Error fetching meals of {}: {}", canteen.name, err);
+                Vec::new()
+            });
+            meals.into_iter().map(|meal| ServedMeal {
+                canteen: canteen.name.clone(),
+                meal,
+            })
+        })
+        .collect()
+}
+
+/// Normalizes text for case- and umlaut-insensitive matching.
+///
+/// Umlauts and their two-letter spellings are folded to the plain vowel,
+/// so `Käse`, `Kaese` and `kase` all match each other.
+///
+/// # Arguments
+///
+/// * `text` - The text to normalize.
+///
+/// # Returns
+///
+/// The normalized text.
+pub fn normalize(text: &str) -> String {
+    let folded: String = text
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'ä' | 'á' | 'à' | 'â' => 'a',
+            'ö' | 'ó' | 'ò' | 'ô' => 'o',
+            'ü' | 'ú' | 'ù' | 'û' => 'u',
+            'é' | 'è' | 'ê' => 'e',
+            _ => c,
+        })
+        .collect();
+
+    folded
+        .replace('ß', "ss")
+        .replace("ae", "a")
+        .replace("oe", "o")
+        .replace("ue", "u")
+}
+
+/// Checks whether a meal name contains a search term.
+///
+/// # Arguments
+///
+/// * `name` - The name of the meal.
+/// * `query` - The search term, surrounding whitespace is ignored.
+///
+/// # Returns
+///
+/// `true` if the normalized name contains the normalized term, `false` for blank terms.
+pub fn matches(name: &str, query: &str) -> bool {
+    let query = normalize(query.trim());
+    !query.is_empty() && normalize(name).contains(&query)
+}
+
+/// Checks whether a category holds sides, desserts or drinks rather than full meals.
+///
+/// # Arguments
+///
+/// * `category` - The name of the category.
+///
+/// # Returns
+///
+/// `true` if the category looks like sides, desserts or drinks.
+pub fn is_side_category(category: &str) -> bool {
+    normalize(category)
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| SIDE_CATEGORIES.contains(&word))
+}
+
+/// Picks the cheapest meals for a price group.
+///
+/// Meals without a price for the group are ignored, and so are meals priced at
+/// zero, which OpenMensa uses for free items and placeholders.
+///
+/// # Arguments
+///
+/// * `meals` - The meals to choose from.
+/// * `price_group` - The price group whose prices are compared.
+/// * `count` - The maximum number of meals to return.
+/// * `full_meals_only` - Whether to leave out sides, desserts and drinks.
+///
+/// # Returns
+///
+/// The meals with their price, cheapest first.
+pub fn cheapest(
+    meals: Vec<ServedMeal>,
+    price_group: PriceGroup,
+    count: usize,
+    full_meals_only: bool,
+) -> Vec<(ServedMeal, f64)> {
+    let mut priced: Vec<(ServedMeal, f64)> = meals
+        .into_iter()
+        .filter(|served| !full_meals_only || !is_side_category(&served.meal.category))
+        .filter_map(|served| {
+            let price = price_group.price(&served.meal.prices)?;
+            (price > 0.0).then_some((served, price))
+        })
+        .collect();
+
+    priced.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+    priced.truncate(count);
+    priced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_folds_case_and_umlauts() {
+        assert_eq!(normalize("Käse"), "kase");
+        assert_eq!(normalize("KAESE"), "kase");
+        assert_eq!(normalize("Grüner Salat"), "gruner salat");
+        assert_eq!(normalize("Crème brûlée"), "creme brulee");
+    }
+
+    #[test]
+    fn normalize_spells_out_sharp_s() {
+        assert_eq!(normalize("Soße"), "sosse");
+        assert_eq!(normalize("Sosse"), "sosse");
+    }
+
+    #[test]
+    fn matches_ignores_case_umlauts_and_surrounding_whitespace() {
+        assert!(matches("Käsespätzle mit Röstzwiebeln", "kaese"));
+        assert!(matches("Käsespätzle mit Röstzwiebeln", "  SPÄTZLE "));
+        assert!(!matches("Käsespätzle mit Röstzwiebeln", "Schnitzel"));
+    }
+
+    #[test]
+    fn matches_nothing_for_blank_queries() {
+        assert!(!matches("Schnitzel", ""));
+        assert!(!matches("Schnitzel", "   "));
+    }
+
+    #[test]
+    fn side_categories_match_whole_words() {
+        assert!(is_side_category("Beilagen"));
+        assert!(is_side_category("Desserts & Getränke"));
+        assert!(is_side_category("Side dishes"));
+        assert!(!is_side_category("Suppen"));
+        assert!(!is_side_category("Salate"));
+        assert!(!is_side_category("Hauptgericht"));
+        assert!(!is_side_category("Seasonal"));
+    }
+
+    /// Builds a meal served in a canteen, priced for students only.
+    fn served(name: &str, category: &str, students: Option<f64>) -> ServedMeal {
+        ServedMeal {
+            canteen: String::from("Mensa"),
+            meal: serde_json::from_value(serde_json::json!({
+                "id": 1,
+                "name": name,
+                "category": category,
+                "prices": {
+                    "students": students,
+                    "employees": null,
+                    "pupils": null,
+                    "others": null
+                },
+                "notes": []
+            }))
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn cheapest_skips_missing_and_zero_prices() {
+        let meals = vec![
+            served("Wasser", "Hauptgericht", Some(0.0)),
+            served("Curry", "Hauptgericht", Some(3.2)),
+            served("Platzhalter", "Hauptgericht", None),
+            served("Pasta", "Hauptgericht", Some(2.5)),
+        ];
+
+        let cheapest = cheapest(meals, PriceGroup::Students, 5, false);
+
+        let names: Vec<&str> = cheapest
+            .iter()
+            .map(|(served, _)| served.meal.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Pasta", "Curry"]);
+    }
+
+    #[test]
+    fn cheapest_leaves_out_sides_for_full_meals_only() {
+        let meals = vec![
+            served("Pommes", "Beilagen", Some(1.0)),
+            served("Tomatensuppe", "Suppen", Some(1.5)),
+            served("Schnitzel", "Hauptgericht", Some(4.0)),
+        ];
+
+        let cheapest = cheapest(meals, PriceGroup::Students, 1, true);
+
+        assert_eq!(cheapest.len(), 1);
+        assert_eq!(cheapest[0].0.meal.name, "Tomatensuppe");
+        assert_eq!(cheapest[0].1, 1.5);
+    }
+}
@@ -1,5 +1,12 @@
+pub mod canteen;
+pub mod comparison;
 pub mod location;
 pub mod meal;
+pub mod menu;
+pub mod price;
 
+pub use canteen::{CanteenInfo, TabledCanteen};
+pub use comparison::Comparison;
 pub use location::Location;
 pub use meal::TabledMeal;
+pub use price::PriceGroup;
@@ -0,0 +1,107 @@
+use crate::i18n::{tr, Language, Message};
+use clap::ValueEnum;
+use openmensa_rust_interface::Prices;
+
+/// The group of people a meal price applies to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, poise::ChoiceParameter)]
+pub enum PriceGroup {
+    /// Prices for students.
+    #[default]
+    #[name = "Students"]
+    #[name_localized("de", "Studierende")]
+    Students,
+    /// Prices for employees of the university.
+    #[name = "Employees"]
+    #[name_localized("de", "Bedienstete")]
+    Employees,
+    /// Prices for pupils.
+    #[name = "Pupils"]
+    #[name_localized("de", "Schüler")]
+    Pupils,
+    /// Prices for guests.
+    #[name = "Others"]
+    #[name_localized("de", "Gäste")]
+    Others,
+}
+
+impl PriceGroup {
+    /// Picks the price of this group.
+    ///
+    /// # Arguments
+    ///
+    /// * `prices` - The prices of a meal.
+    ///
+    /// # Returns
+    ///
+    /// The price, or `None` if the canteen does not list one for this group.
+    pub fn price(self, prices: &Prices) -> Option<f64> {
+        match self {
+            PriceGroup::Students => prices.students,
+            PriceGroup::Employees => prices.employees,
+            PriceGroup::Pupils => prices.pupils,
+            PriceGroup::Others => prices.others,
+        }
+    }
+
+    /// Returns the localized name of this group.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The language of the name.
+    ///
+    /// # Returns
+    ///
+    /// The name, e.g. `Students`.
+    pub fn label(self, language: Language) -> &'static str {
+        let message = match self {
+            PriceGroup::Students => Message::Students,
+            PriceGroup::Employees => Message::Employees,
+            PriceGroup::Pupils => Message::Pupils,
+            PriceGroup::Others => Message::Others,
+        };
+        tr(language, message)
+    }
+}
+
+/// Formats the known prices of all groups.
+///
+/// # Arguments
+///
+/// * `prices` - The prices of a meal.
+/// * `language` - The language of the group names.
+///
+/// # Returns
+///
+/// The prices, e.g. `Students: 2.50 €, Others: 4.90 €`, or `-` if none are known.
+pub fn format_prices(prices: &Prices, language: Language) -> String {
+    let known: Vec<String> = PriceGroup::value_variants()
+        .iter()
+        .filter_map(|group| {
+            group
+                .price(prices)
+                .map(|price| format!("{}: {}", group.label(language), format_price(Some(price))))
+        })
+        .collect();
+
+    if known.is_empty() {
+        String::from("-")
+    } else {
+        known.join(", ")
+    }
+}
+
+/// Formats a price in euros.
+///
+/// # Arguments
+///
+/// * `price` - The price, `None` if unknown.
+///
+/// # Returns
+///
+/// The price with two decimals, e.g. `2.50 €`, or `-`.
+pub fn format_price(price: Option<f64>) -> String {
+    match price {
+        Some(price) => format!("{:.2} €", price),
+        None => String::from("-"),
+    }
+}
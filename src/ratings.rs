@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// File inside the data directory that stores the meal ratings.
+pub const RATINGS_FILE: &str = "meal_ratings.json";
+
+/// A rating of a meal by a single user.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Rating {
+    /// Number of stars from 1 to 5.
+    pub stars: u8,
+    /// Optional review text.
+    pub comment: Option<String>,
+    /// Unix timestamp of when the rating was given.
+    pub rated_at: i64,
+}
+
+/// Ratings of all meals, keyed by `ratings::key`, then by user ID.
+pub type Ratings = HashMap<String, HashMap<String, Rating>>;
+
+/// Builds the key meals are rated under.
+///
+/// Meal names are compared case-insensitively, so the same dish on another day shares its ratings.
+///
+/// # Arguments
+///
+/// * `canteen_id` - The OpenMensa ID of the canteen.
+/// * `meal` - The name of the meal.
+///
+/// # Returns
+///
+/// The key of the meal in the ratings.
+pub fn key(canteen_id: u32, meal: &str) -> String {
+    format!("{}/{}", canteen_id, meal.trim().to_lowercase())
+}
+
+/// Calculates the average rating of a meal.
+///
+/// # Arguments
+///
+/// * `ratings` - All meal ratings.
+/// * `canteen_id` - The OpenMensa ID of the canteen.
+/// * `meal` - The name of the meal.
+///
+/// # Returns
+///
+/// The average number of stars and the number of ratings, or `None` if the meal was never rated.
+pub fn average(ratings: &Ratings, canteen_id: u32, meal: &str) -> Option<(f64, usize)> {
+    let meal_ratings = ratings.get(&key(canteen_id, meal))?;
+    if meal_ratings.is_empty() {
+        return None;
+    }
+
+    let total: u32 = meal_ratings
+        .values()
+        .map(|rating| u32::from(rating.stars))
+        .sum();
+    Some((
+        f64::from(total) / meal_ratings.len() as f64,
+        meal_ratings.len(),
+    ))
+}
+
+/// Formats the average rating of a meal for display.
+///
+/// # Arguments
+///
+/// * `ratings` - All meal ratings.
+/// * `canteen_id` - The OpenMensa ID of the canteen.
+/// * `meal` - The name of the meal.
+///
+/// # Returns
+///
+/// The rating like `★ 4.2 (5)`, or `-` if the meal was never rated.
+pub fn format_average(ratings: &Ratings, canteen_id: u32, meal: &str) -> String {
+    match average(ratings, canteen_id, meal) {
+        Some((stars, count)) => format!("★ {:.1} ({})", stars, count),
+        None => String::from("-"),
+    }
+}
+
+/// Finds the most recent review text of a meal.
+///
+/// # Arguments
+///
+/// * `ratings` - All meal ratings.
+/// * `canteen_id` - The OpenMensa ID of the canteen.
+/// * `meal` - The name of the meal.
+///
+/// # Returns
+///
+/// The newest comment, or `None` if nobody left one.
+pub fn latest_comment(ratings: &Ratings, canteen_id: u32, meal: &str) -> Option<String> {
+    ratings
+        .get(&key(canteen_id, meal))?
+        .values()
+        .filter(|rating| rating.comment.is_some())
+        .max_by_key(|rating| rating.rated_at)
+        .and_then(|rating| rating.comment.clone())
+}
@@ -0,0 +1,325 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Environment variable holding the bot token.
+pub const TOKEN_VAR: &str = "DISCORD_TOKEN";
+
+/// Environment variable holding the path of a file with the bot token, e.g. a Docker secret.
+pub const TOKEN_FILE_VAR: &str = "DISCORD_TOKEN_FILE";
+
+/// Env file read if `--env-file` is not passed.
+const DEFAULT_ENV_FILE: &str = ".env";
+
+/// A secret value that is never printed.
+///
+/// `Debug` and `Display` only show a placeholder, the value itself is available via `expose`.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    /// Returns the secret value.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Secret(value)
+    }
+}
+
+impl FromStr for Secret {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(Secret(value.to_string()))
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret([redacted])")
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[redacted]")
+    }
+}
+
+/// Where the bot token was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    /// The `--token` argument.
+    Flag,
+    /// The `DISCORD_TOKEN` environment variable.
+    Env,
+    /// An env file, either passed with `--env-file` or `.env` in the working directory.
+    EnvFile(PathBuf),
+    /// `bot.token` in the configuration.
+    Config,
+    /// The file named by `DISCORD_TOKEN_FILE`.
+    SecretFile(PathBuf),
+}
+
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenSource::Flag => write!(f, "--token"),
+            TokenSource::Env => write!(f, "${}", TOKEN_VAR),
+            TokenSource::EnvFile(path) => write!(f, "env file {}", path.display()),
+            TokenSource::Config => write!(f, "bot.token in the config file"),
+            TokenSource::SecretFile(path) => write!(f, "secret file {}", path.display()),
+        }
+    }
+}
+
+/// Reads `DISCORD_TOKEN` from an env file without changing the environment.
+///
+/// # Arguments
+///
+/// * `path` - The path of the env file.
+///
+/// # Returns
+///
+/// The token if the file sets one, otherwise `Ok(None)`, or `Err(String)` if the file can not be read.
+fn read_env_file(path: &Path) -> Result<Option<Secret>, String> {
+    // `from_path` would export every variable of the file, the iterator only parses it
+    let entries = dotenvy::from_path_iter(path)
+        .map_err(|err| format!("Failed to read env file {}: {}", path.display(), err))?;
+
+    for entry in entries {
+        let (key, value) =
+            entry.map_err(|err| format!("Failed to parse env file {}: {}", path.display(), err))?;
+        if key == TOKEN_VAR {
+            return Ok(Some(Secret(value)));
+        }
+    }
+    Ok(None)
+}
+
+/// Resolves the bot token from all supported sources.
+///
+/// The first source that provides a non-empty token wins, in this order: the `--token` flag,
+/// `$DISCORD_TOKEN`, the env file, `bot.token` in the configuration and the file named by
+/// `$DISCORD_TOKEN_FILE`.
+///
+/// # Arguments
+///
+/// * `flag` - The token passed with `--token`.
+/// * `env_file` - The path passed with `--env-file`, `.env` is used if it is `None`.
+/// * `configured` - The token from the configuration.
+/// * `env` - Looks up environment variables, usually `|name| std::env::var(name).ok()`.
+///
+/// # Returns
+///
+/// The token and its source, otherwise returns `Err(String)`.
+pub fn resolve_token(
+    flag: Option<&Secret>,
+    env_file: Option<&str>,
+    configured: Option<&Secret>,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<(Secret, TokenSource), String> {
+    let present = |secret: &Secret| !secret.expose().trim().is_empty();
+
+    if let Some(token) = flag.filter(|token| present(token)) {
+        return Ok((token.clone(), TokenSource::Flag));
+    }
+
+    if let Some(token) = env(TOKEN_VAR).map(Secret).filter(present) {
+        return Ok((token, TokenSource::Env));
+    }
+
+    // An explicitly passed env file has to exist, the default one is optional
+    let env_path = PathBuf::from(env_file.unwrap_or(DEFAULT_ENV_FILE));
+    if env_file.is_some() && !env_path.exists() {
+        return Err(format!("Env file {} does not exist", env_path.display()));
+    }
+    if env_path.exists() {
+        if let Some(token) = read_env_file(&env_path)?.filter(present) {
+            return Ok((token, TokenSource::EnvFile(env_path)));
+        }
+    }
+
+    if let Some(token) = configured.filter(|token| present(token)) {
+        return Ok((token.clone(), TokenSource::Config));
+    }
+
+    if let Some(path) = env(TOKEN_FILE_VAR) {
+        let path = PathBuf::from(path);
+        let token = fs::read_to_string(&path)
+            .map_err(|err| format!("Failed to read secret file {}: {}", path.display(), err))?;
+        let token = Secret(token.trim().to_string());
+        if present(&token) {
+            return Ok((token, TokenSource::SecretFile(path)));
+        }
+    }
+
+    Err(format!(
+        "Please provide a Discord token with --token, ${}, an env file, bot.token in the config file or ${}",
+        TOKEN_VAR, TOKEN_FILE_VAR
+    ))
+}
+
+/// Checks whether a token looks like a Discord bot token.
+///
+/// Bot tokens consist of three base64 parts separated by dots, the first of which
+/// encodes the ID of the bot user. The token is not sent to Discord.
+///
+/// # Arguments
+///
+/// * `token` - The token to check.
+///
+/// # Returns
+///
+/// The ID of the bot user, or a description of the problem as `Err(String)`.
+pub fn check_token_format(token: &Secret) -> Result<u64, String> {
+    let token = token.expose();
+    if token.trim() != token {
+        return Err("The token contains surrounding whitespace".into());
+    }
+    if token.starts_with("Bot ") {
+        return Err("The token must not start with \"Bot \"".into());
+    }
+
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 || parts.iter().any(|part| part.is_empty()) {
+        return Err("The token must consist of three parts separated by dots".into());
+    }
+
+    let valid_chars = |part: &&str| {
+        part.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    };
+    if !parts.iter().all(valid_chars) {
+        return Err("The token contains invalid characters".into());
+    }
+
+    URL_SAFE_NO_PAD
+        .decode(parts[0])
+        .ok()
+        .and_then(|id| String::from_utf8(id).ok())
+        .and_then(|id| id.parse::<u64>().ok())
+        .ok_or_else(|| "The first part of the token does not encode a bot user ID".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a file into the temporary directory and returns its path.
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("discord-bot-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    /// Builds a token with the given bot user ID in its first part.
+    fn token_for(id: &str) -> Secret {
+        Secret(format!("{}.GhIjKl.abc_DEF-123", URL_SAFE_NO_PAD.encode(id)))
+    }
+
+    #[test]
+    fn debug_and_display_hide_the_value() {
+        let secret = Secret::from(String::from("super-secret"));
+
+        assert!(!format!("{:?}", secret).contains("super-secret"));
+        assert!(!format!("{}", secret).contains("super-secret"));
+        assert_eq!(secret.expose(), "super-secret");
+    }
+
+    #[test]
+    fn resolve_token_follows_the_precedence() {
+        let env_file = temp_file("token.env", "DISCORD_TOKEN=from-env-file\n");
+        let empty_env_file = temp_file("empty.env", "OTHER=value\n");
+        let secret_file = temp_file("token.secret", "from-secret-file\n");
+        let env_path = env_file.to_str();
+        let empty_path = empty_env_file.to_str();
+        let flag = Secret::from(String::from("from-flag"));
+        let blank = Secret::from(String::from("  "));
+        let configured = Secret::from(String::from("from-config"));
+
+        let no_env = |_: &str| None;
+        let token_env = |name: &str| (name == TOKEN_VAR).then(|| String::from("from-env"));
+        let file_env = |name: &str| {
+            (name == TOKEN_FILE_VAR).then(|| secret_file.to_string_lossy().into_owned())
+        };
+        let resolve = |flag, env_file, configured, env: &dyn Fn(&str) -> Option<String>| {
+            let (token, source) = resolve_token(flag, env_file, configured, env).unwrap();
+            (token.expose().to_string(), source)
+        };
+
+        assert_eq!(
+            resolve(Some(&flag), env_path, Some(&configured), &token_env),
+            (String::from("from-flag"), TokenSource::Flag)
+        );
+        assert_eq!(
+            resolve(Some(&blank), env_path, Some(&configured), &token_env),
+            (String::from("from-env"), TokenSource::Env)
+        );
+        assert_eq!(
+            resolve(None, env_path, Some(&configured), &no_env),
+            (
+                String::from("from-env-file"),
+                TokenSource::EnvFile(env_file.clone())
+            )
+        );
+        assert_eq!(
+            resolve(None, empty_path, Some(&configured), &file_env),
+            (String::from("from-config"), TokenSource::Config)
+        );
+        assert_eq!(
+            resolve(None, empty_path, None, &file_env),
+            (
+                String::from("from-secret-file"),
+                TokenSource::SecretFile(secret_file.clone())
+            )
+        );
+
+        assert!(resolve_token(None, empty_path, None, no_env).is_err());
+        assert!(resolve_token(None, Some("/nonexistent/.env"), Some(&configured), no_env).is_err());
+
+        for path in [env_file, empty_env_file, secret_file] {
+            fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn check_token_format_returns_the_bot_user_id() {
+        assert_eq!(
+            check_token_format(&token_for("123456789012345678")),
+            Ok(123456789012345678)
+        );
+    }
+
+    #[test]
+    fn check_token_format_rejects_malformed_tokens() {
+        let valid = token_for("123456789012345678").expose().to_string();
+        let malformed = [
+            format!(" {}", valid),
+            format!("Bot {}", valid),
+            String::from("abc.def"),
+            String::from("abc..def"),
+            valid.replace('_', "+"),
+            token_for("not-an-id").expose().to_string(),
+        ];
+
+        for token in malformed {
+            assert!(
+                check_token_format(&Secret::from(token.clone())).is_err(),
+                "accepted {:?}",
+                token
+            );
+        }
+    }
+}
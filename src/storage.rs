@@ -0,0 +1,230 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// Returns the directory where persistent bot state is stored.
+///
+/// Honours `$XDG_DATA_HOME` and falls back to `~/.local/share/discord-bot`.
+///
+/// # Returns
+///
+/// The path of the data directory (it is not created by this function).
+pub fn data_dir() -> PathBuf {
+    match std::env::var("XDG_DATA_HOME") {
+        Ok(dir) if !dir.is_empty() => Path::new(&dir).join("discord-bot"),
+        _ => PathBuf::from(shellexpand::tilde("~/.local/share/discord-bot").into_owned()),
+    }
+}
+
+/// A value that is kept in memory and persisted as JSON inside the data directory.
+pub struct Store<T> {
+    /// The file the value is persisted to.
+    path: PathBuf,
+    /// The in-memory value.
+    value: Mutex<T>,
+    /// Held while writing, so an older state never overwrites a newer one.
+    write: Mutex<()>,
+}
+
+impl<T> Store<T>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    /// Opens the store with the given file name, loading its last persisted state.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The file name inside the data directory, e.g. `votes.json`.
+    ///
+    /// # Returns
+    ///
+    /// The opened store, holding the default value if the file does not exist.
+    /// A file that can not be parsed is moved to `<name>.corrupt` first, so the next
+    /// flush does not destroy it. If that fails or the file can not be read,
+    /// an error message is returned as a `String`.
+    pub fn open(name: &str) -> Result<Self, String> {
+        Self::open_in(&data_dir(), name)
+    }
+
+    /// Opens the store with the given file name inside a directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory holding the file.
+    /// * `name` - The file name inside the directory.
+    ///
+    /// # Returns
+    ///
+    /// The opened store, see [`Store::open`].
+    fn open_in(dir: &Path, name: &str) -> Result<Self, String> {
+        let path = dir.join(name);
+        let parsed = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|err| err.to_string()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+            // The file is readable but no UTF-8, so it is as corrupt as invalid JSON
+            Err(err) if err.kind() == io::ErrorKind::InvalidData => Err(err.to_string()),
+            // Starting over would overwrite the intact but unreadable file on the next flush
+            Err(err) => return Err(format!("Failed to read {}: {}", path.display(), err)),
+        };
+
+        let value = match parsed {
+            Ok(value) => value,
+            Err(err) => {
+                let backup = dir.join(format!("{}.corrupt", name));
+                fs::rename(&path, &backup).map_err(|rename_err| {
+                    format!(
+                        "Failed to parse {} ({}) and to move it to {}: {}",
+                        path.display(),
+                        err,
+                        backup.display(),
+                        rename_err
+                    )
+                })?;
+                eprintln!(
+                    "Failed to parse {}: {}, moved it to {} and started over",
+                    path.display(),
+                    err,
+                    backup.display()
+                );
+                T::default()
+            }
+        };
+
+        Ok(Store {
+            path,
+            value: Mutex::new(value),
+            write: Mutex::new(()),
+        })
+    }
+
+    /// Runs a closure with read access to the stored value.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The closure receiving a reference to the value.
+    ///
+    /// # Returns
+    ///
+    /// Whatever the closure returns.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        let value = self.value.lock().unwrap();
+        f(&value)
+    }
+
+    /// Modifies the stored value and writes it to disk afterwards.
+    ///
+    /// The file is written on the blocking thread pool, so the async runtime is not stalled.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The closure receiving a mutable reference to the value.
+    ///
+    /// # Returns
+    ///
+    /// Whatever the closure returns.
+    pub async fn update<R>(self: &Arc<Self>, f: impl FnOnce(&mut T) -> R) -> R
+    where
+        T: Send + 'static,
+    {
+        let result = {
+            let mut value = self.value.lock().unwrap();
+            f(&mut value)
+        };
+
+        let store = Arc::clone(self);
+        match tokio::task::spawn_blocking(move || store.flush()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => eprintln!("{}", err),
+            Err(err) => eprintln!("Failed to write {}: {}", self.path.display(), err),
+        }
+        result
+    }
+
+    /// Writes the current value to disk.
+    ///
+    /// The file is written to a temporary path first and then renamed,
+    /// so a crash never leaves a half-written file behind.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the value was written, otherwise an error message as a `String`.
+    pub fn flush(&self) -> Result<(), String> {
+        let _write = self.write.lock().unwrap();
+        let contents = {
+            let value = self.value.lock().unwrap();
+            serde_json::to_string_pretty(&*value)
+                .map_err(|err| format!("Failed to serialize {}: {}", self.path.display(), err))?
+        };
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("Failed to create {}: {}", parent.display(), err))?;
+        }
+
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, contents)
+            .and_then(|_| fs::rename(&tmp_path, &self.path))
+            .map_err(|err| format!("Failed to write {}: {}", self.path.display(), err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Creates an empty directory for a test.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("discord-bot-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn corrupt_files_are_moved_aside() {
+        let dir = test_dir("corrupt");
+        fs::write(dir.join("votes.json"), "{ not json").unwrap();
+
+        let store = Store::<HashMap<String, u32>>::open_in(&dir, "votes.json").unwrap();
+
+        assert!(store.with(HashMap::is_empty));
+        assert!(!dir.join("votes.json").exists());
+        assert_eq!(
+            fs::read_to_string(dir.join("votes.json.corrupt")).unwrap(),
+            "{ not json"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_files_start_empty_and_flush_round_trips() {
+        let dir = test_dir("round-trip");
+
+        let store = Store::<HashMap<String, u32>>::open_in(&dir, "votes.json").unwrap();
+        assert!(store.with(HashMap::is_empty));
+        store.value.lock().unwrap().insert(String::from("meme"), 3);
+        store.flush().unwrap();
+
+        let reopened = Store::<HashMap<String, u32>>::open_in(&dir, "votes.json").unwrap();
+        assert_eq!(reopened.with(|votes| votes.get("meme").copied()), Some(3));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn unreadable_files_are_left_alone() {
+        let dir = test_dir("unreadable");
+        // A directory can not be read as a file, like a file without read permission
+        fs::create_dir(dir.join("votes.json")).unwrap();
+
+        let result = Store::<HashMap<String, u32>>::open_in(&dir, "votes.json");
+
+        assert!(result.is_err());
+        assert!(dir.join("votes.json").is_dir());
+        assert!(!dir.join("votes.json.corrupt").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}